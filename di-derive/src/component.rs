@@ -1,232 +1,683 @@
+use crate::validators::{self, Validator};
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::{
-    Attribute, Data, DeriveInput, Error, Fields, GenericArgument, Meta, NestedMeta, Path,
-    PathArguments, Result, Type,
+    Attribute, Data, DeriveInput, Error, Field, Fields, GenericArgument, Meta, NestedMeta, Path,
+    PathArguments, Result, Token, Type,
 };
 
 struct ComponentArgs {
     name: Option<String>,
-    interface: Path,
+    interfaces: Vec<Path>,
     init: Option<Path>,
+    condition: Option<Path>,
+    auto_register: bool,
 }
 
 enum ValueDefault {
     None,
     Default,
     DefaultCall(Path),
+    Literal(syn::Lit),
+    Str(syn::LitStr),
+    Json(syn::LitStr),
 }
 
 enum InjectArgs {
     InjectValue {
         name: Option<String>,
         default: ValueDefault,
+        validators: Vec<Validator>,
+        flatten: bool,
     },
     InjectComponent {
         name: Option<String>,
     },
+    ContextValue {
+        name: Option<String>,
+    },
+}
+
+const COMPONENT_ATTRIBUTES: &[&str] = &["name", "interface", "init", "condition", "auto_register"];
+const VALUE_ATTRIBUTES: &[&str] = &[
+    "name",
+    "default",
+    "default_str",
+    "default_json",
+    "flatten",
+    "range",
+    "regex",
+    "non_empty",
+    "one_of",
+    "validate",
+];
+const INJECT_ATTRIBUTES: &[&str] = &["name", "from_context"];
+
+/// An item of `#[value(...)]`.
+///
+/// In addition to the regular meta syntax, this accepts `key = [lit, ...]`.
+pub(crate) enum ValueItem {
+    Meta(NestedMeta),
+    Array(Path, Vec<syn::Lit>),
+}
+
+impl Parse for ValueItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(syn::Ident) && input.peek2(Token![=]) && input.peek3(syn::token::Bracket) {
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let content;
+            syn::bracketed!(content in input);
+            let items = Punctuated::<syn::Lit, Token![,]>::parse_terminated(&content)?;
+            Ok(ValueItem::Array(ident.into(), items.into_iter().collect()))
+        } else {
+            Ok(ValueItem::Meta(input.parse()?))
+        }
+    }
+}
+
+pub(crate) fn unknown_attribute(path: &Path, attr_name: &str, expected: &[&str]) -> Error {
+    let key = path.to_token_stream().to_string().replace(' ', "");
+    let suggestion = expected
+        .iter()
+        .map(|name| (strsim::levenshtein(&key, name), *name))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance);
+    let message = match suggestion {
+        Some((_, name)) => format!(
+            "Unknown attribute '{}' in '#[{}(...)]', did you mean '{}'?",
+            key, attr_name, name
+        ),
+        None => format!(
+            "Unknown attribute '{}' in '#[{}(...)]', expected one of: {}",
+            key,
+            attr_name,
+            expected
+                .iter()
+                .map(|name| format!("'{}'", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    Error::new_spanned(path, message)
+}
+
+pub(crate) fn nested_path(meta: &NestedMeta) -> Result<&Path> {
+    match meta {
+        NestedMeta::Meta(meta) => Ok(meta.path()),
+        NestedMeta::Lit(lit) => Err(Error::new_spanned(
+            lit,
+            "Unexpected literal, expected an attribute such as 'name = \"...\"'.",
+        )),
+    }
+}
+
+pub(crate) fn expect_name_value<'a>(meta: &'a NestedMeta, key: &str) -> Result<&'a syn::Lit> {
+    match meta {
+        NestedMeta::Meta(Meta::NameValue(nv)) => Ok(&nv.lit),
+        _ => Err(Error::new_spanned(
+            meta,
+            format!(
+                "Attribute '{}' expects a value, e.g. '{} = \"...\"'.",
+                key, key
+            ),
+        )),
+    }
+}
+
+pub(crate) fn parse_string(lit: &syn::Lit, key: &str) -> Result<String> {
+    match lit {
+        syn::Lit::Str(lit) => Ok(lit.value()),
+        _ => Err(Error::new_spanned(
+            lit,
+            format!("Attribute '{}' should be a string.", key),
+        )),
+    }
+}
+
+pub(crate) fn parse_path(lit: &syn::Lit, key: &str, example: &str) -> Result<Path> {
+    match lit {
+        syn::Lit::Str(lit) => lit.parse::<Path>().map_err(|_| {
+            Error::new_spanned(
+                lit,
+                format!(
+                    "Attribute '{}' should be a path, e.g. '{} = \"{}\"'.",
+                    key, key, example
+                ),
+            )
+        }),
+        _ => Err(Error::new_spanned(
+            lit,
+            format!("Attribute '{}' should be a string.", key),
+        )),
+    }
+}
+
+fn set_once<T>(slot: &mut Option<T>, value: T, meta: &NestedMeta, key: &str) -> Result<()> {
+    if slot.is_some() {
+        return Err(Error::new_spanned(
+            meta,
+            format!("Duplicate attribute '{}'.", key),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
 }
 
 fn parse_component_args(input: &DeriveInput) -> Result<ComponentArgs> {
     let mut name = None;
-    let mut interface = None;
+    let mut interfaces = Vec::new();
     let mut init = None;
+    let mut condition = None;
+    let mut auto_register = false;
 
-    for attr in &input.attrs {
-        match attr.parse_meta()? {
-            Meta::List(ls) if ls.path.is_ident("di") => {
-                for meta in &ls.nested {
-                    match meta {
-                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
-                            if let syn::Lit::Str(lit) = &nv.lit {
-                                name = Some(lit.value());
-                            } else {
-                                return Err(Error::new_spanned(
-                                    &nv.lit,
-                                    "Attribute 'name' should be a string.",
-                                ));
-                            }
-                        }
-                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("interface") => {
-                            if let syn::Lit::Str(lit) = &nv.lit {
-                                if let Ok(path) = syn::parse_str::<syn::Path>(&lit.value()) {
-                                    interface = Some(path);
-                                } else {
-                                    return Err(Error::new_spanned(&lit, "Expect path"));
-                                }
-                            } else {
-                                return Err(Error::new_spanned(
-                                    &nv.lit,
-                                    "Attribute 'type' should be a string.",
-                                ));
-                            }
-                        }
-                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("init") => {
-                            if let syn::Lit::Str(lit) = &nv.lit {
-                                if let Ok(path) = syn::parse_str::<syn::Path>(&lit.value()) {
-                                    init = Some(path);
-                                } else {
-                                    return Err(Error::new_spanned(&lit, "Expect path"));
-                                }
-                            } else {
-                                return Err(Error::new_spanned(
-                                    &nv.lit,
-                                    "Attribute 'init' should be a string.",
-                                ));
-                            }
-                        }
-                        _ => {}
-                    }
-                }
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("di")) {
+        let ls = match attr.parse_meta()? {
+            Meta::List(ls) => ls,
+            meta => {
+                return Err(Error::new_spanned(
+                    meta,
+                    "Expected a list of attributes, e.g. '#[di(interface = \"Trait\")]'.",
+                ))
             }
+        };
 
-            _ => {}
+        for meta in &ls.nested {
+            let path = nested_path(meta)?;
+            if path.is_ident("name") {
+                let lit = expect_name_value(meta, "name")?;
+                set_once(&mut name, parse_string(lit, "name")?, meta, "name")?;
+            } else if path.is_ident("interface") {
+                let lit = expect_name_value(meta, "interface")?;
+                interfaces.push(parse_path(lit, "interface", "MyTrait")?);
+            } else if path.is_ident("init") {
+                let lit = expect_name_value(meta, "init")?;
+                set_once(&mut init, parse_path(lit, "init", "init_fn")?, meta, "init")?;
+            } else if path.is_ident("condition") {
+                let lit = expect_name_value(meta, "condition")?;
+                set_once(
+                    &mut condition,
+                    parse_path(lit, "condition", "condition_fn")?,
+                    meta,
+                    "condition",
+                )?;
+            } else if path.is_ident("auto_register") {
+                if !matches!(meta, NestedMeta::Meta(Meta::Path(_))) {
+                    return Err(Error::new_spanned(
+                        meta,
+                        "Attribute 'auto_register' does not accept a value.",
+                    ));
+                }
+                if !input.generics.params.is_empty() {
+                    return Err(Error::new_spanned(
+                        meta,
+                        "Attribute 'auto_register' is not supported for generic components, \
+                         register each instantiation with 'register_as' instead.",
+                    ));
+                }
+                auto_register = true;
+            } else {
+                return Err(unknown_attribute(path, "di", COMPONENT_ATTRIBUTES));
+            }
         }
     }
 
     Ok(ComponentArgs {
         name,
-        interface: interface
-            .ok_or_else(|| Error::new_spanned(input, "Missing 'interface' attribute"))?,
+        interfaces,
         init,
+        condition,
+        auto_register,
     })
 }
 
-fn parse_inject_args(attrs: &[Attribute]) -> Result<Option<InjectArgs>> {
-    for attr in attrs {
-        match attr.parse_meta()? {
-            Meta::Path(path) if path.is_ident("value") => {
-                return Ok(Some(InjectArgs::InjectValue {
-                    name: None,
-                    default: ValueDefault::None,
-                }));
+fn parse_value_args(attr: &Attribute) -> Result<InjectArgs> {
+    let mut name = None;
+    let mut default = None;
+    let mut validators = Vec::new();
+    let mut flatten = false;
+
+    if attr.tokens.is_empty() {
+        return Ok(InjectArgs::InjectValue {
+            name,
+            default: ValueDefault::None,
+            validators,
+            flatten,
+        });
+    }
+
+    let items = attr
+        .parse_args_with(Punctuated::<ValueItem, Token![,]>::parse_terminated)
+        .map_err(|err| {
+            Error::new(
+                err.span(),
+                format!("Expected '#[value]' or '#[value(...)]': {}", err),
+            )
+        })?;
+
+    for item in &items {
+        let meta = match item {
+            ValueItem::Meta(meta) => meta,
+            ValueItem::Array(path, items) if path.is_ident("one_of") => {
+                validators.push(Validator::OneOf(items.clone()));
+                continue;
             }
-            Meta::Path(path) if path.is_ident("inject") => {
-                return Ok(Some(InjectArgs::InjectComponent { name: None }));
+            ValueItem::Array(path, _) => {
+                return Err(Error::new_spanned(
+                    path,
+                    format!(
+                        "Attribute '{}' does not accept a list.",
+                        path.to_token_stream()
+                    ),
+                ))
             }
-            Meta::List(ls) if ls.path.is_ident("value") => {
-                let mut name = None;
-                let mut default = ValueDefault::None;
-
-                for meta in &ls.nested {
-                    match meta {
-                        NestedMeta::Meta(Meta::Path(p)) if p.is_ident("default") => {
-                            default = ValueDefault::Default;
-                        }
-                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
-                            if let syn::Lit::Str(lit) = &nv.lit {
-                                name = Some(lit.value());
-                            } else {
-                                return Err(Error::new_spanned(
-                                    &nv.lit,
-                                    "Attribute 'name' should be a string.",
-                                ));
-                            }
-                        }
-                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("default") => {
-                            if let syn::Lit::Str(lit) = &nv.lit {
-                                if let Ok(path) = syn::parse_str::<syn::Path>(&lit.value()) {
-                                    default = ValueDefault::DefaultCall(path);
-                                } else {
-                                    return Err(Error::new_spanned(&lit, "Expect ident"));
-                                }
-                            } else {
-                                return Err(Error::new_spanned(
-                                    &nv.lit,
-                                    "Attribute 'default' should be a string.",
-                                ));
-                            }
-                        }
-                        _ => {}
+        };
+        let path = nested_path(meta)?;
+        if path.is_ident("name") {
+            let lit = expect_name_value(meta, "name")?;
+            set_once(&mut name, parse_string(lit, "name")?, meta, "name")?;
+        } else if path.is_ident("default") {
+            let value = match meta {
+                NestedMeta::Meta(Meta::Path(_)) => ValueDefault::Default,
+                NestedMeta::Meta(Meta::NameValue(nv)) => match &nv.lit {
+                    syn::Lit::Str(lit) => {
+                        ValueDefault::DefaultCall(lit.parse::<Path>().map_err(|_| {
+                            Error::new_spanned(
+                                lit,
+                                "Attribute 'default' should be a function path, e.g. 'default = \"default_fn\"', \
+                                 use 'default_str = \"...\"' for a string literal.",
+                            )
+                        })?)
+                    }
+                    syn::Lit::Int(_) | syn::Lit::Float(_) | syn::Lit::Bool(_) | syn::Lit::Char(_) => {
+                        ValueDefault::Literal(nv.lit.clone())
                     }
+                    lit => {
+                        return Err(Error::new_spanned(
+                            lit,
+                            "Attribute 'default' should be a number, a boolean, a char or a function path.",
+                        ))
+                    }
+                },
+                _ => {
+                    return Err(Error::new_spanned(
+                        meta,
+                        "Expected 'default', 'default = 8080' or 'default = \"default_fn\"'.",
+                    ))
                 }
-
-                return Ok(Some(InjectArgs::InjectValue { name, default }));
+            };
+            set_once(&mut default, value, meta, "default")?;
+        } else if path.is_ident("default_str") {
+            let lit = match expect_name_value(meta, "default_str")? {
+                syn::Lit::Str(lit) => lit.clone(),
+                lit => {
+                    return Err(Error::new_spanned(
+                        lit,
+                        "Attribute 'default_str' should be a string.",
+                    ))
+                }
+            };
+            set_once(&mut default, ValueDefault::Str(lit), meta, "default")?;
+        } else if path.is_ident("default_json") {
+            let lit = match expect_name_value(meta, "default_json")? {
+                syn::Lit::Str(lit) => lit.clone(),
+                lit => {
+                    return Err(Error::new_spanned(
+                        lit,
+                        "Attribute 'default_json' should be a string.",
+                    ))
+                }
+            };
+            if let Err(err) = serde_json::from_str::<serde_json::Value>(&lit.value()) {
+                return Err(Error::new_spanned(
+                    &lit,
+                    format!("Attribute 'default_json' is not valid JSON: {}", err),
+                ));
+            }
+            set_once(&mut default, ValueDefault::Json(lit), meta, "default")?;
+        } else if path.is_ident("flatten") {
+            if !matches!(meta, NestedMeta::Meta(Meta::Path(_))) {
+                return Err(Error::new_spanned(
+                    meta,
+                    "Attribute 'flatten' does not accept a value.",
+                ));
             }
+            flatten = true;
+        } else if let Some(validator) = validators::parse(meta)? {
+            validators.push(validator);
+        } else {
+            return Err(unknown_attribute(path, "value", VALUE_ATTRIBUTES));
+        }
+    }
 
-            Meta::List(ls) if ls.path.is_ident("inject") => {
-                let mut name = None;
-
-                for meta in &ls.nested {
-                    match meta {
-                        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
-                            if let syn::Lit::Str(lit) = &nv.lit {
-                                name = Some(lit.value());
-                            } else {
-                                return Err(Error::new_spanned(
-                                    &nv.lit,
-                                    "Attribute 'name' should be a string.",
-                                ));
-                            }
-                        }
-                        _ => {}
-                    }
-                }
+    if flatten && (name.is_some() || default.is_some()) {
+        return Err(Error::new_spanned(
+            attr,
+            "Attribute 'flatten' cannot be combined with 'name' or a default value.",
+        ));
+    }
+
+    Ok(InjectArgs::InjectValue {
+        name,
+        default: default.unwrap_or(ValueDefault::None),
+        validators,
+        flatten,
+    })
+}
+
+fn parse_inject_component_args(meta: Meta) -> Result<InjectArgs> {
+    let mut name = None;
+    let mut from_context = false;
+
+    let ls = match meta {
+        Meta::Path(_) => return Ok(InjectArgs::InjectComponent { name }),
+        Meta::List(ls) => ls,
+        Meta::NameValue(nv) => {
+            return Err(Error::new_spanned(
+                nv,
+                "Expected '#[inject]' or '#[inject(...)]'.",
+            ))
+        }
+    };
 
-                return Ok(Some(InjectArgs::InjectComponent { name }));
+    for meta in &ls.nested {
+        let path = nested_path(meta)?;
+        if path.is_ident("name") {
+            let lit = expect_name_value(meta, "name")?;
+            set_once(&mut name, parse_string(lit, "name")?, meta, "name")?;
+        } else if path.is_ident("from_context") {
+            if !matches!(meta, NestedMeta::Meta(Meta::Path(_))) {
+                return Err(Error::new_spanned(
+                    meta,
+                    "Attribute 'from_context' does not accept a value.",
+                ));
             }
+            from_context = true;
+        } else {
+            return Err(unknown_attribute(path, "inject", INJECT_ATTRIBUTES));
+        }
+    }
+
+    if from_context {
+        Ok(InjectArgs::ContextValue { name })
+    } else {
+        Ok(InjectArgs::InjectComponent { name })
+    }
+}
+
+fn parse_inject_args(attrs: &[Attribute]) -> Result<Option<InjectArgs>> {
+    let mut args = None;
+
+    for attr in attrs {
+        let parsed = if attr.path.is_ident("value") {
+            parse_value_args(attr)?
+        } else if attr.path.is_ident("inject") {
+            parse_inject_component_args(attr.parse_meta()?)?
+        } else {
+            continue;
+        };
 
-            _ => {}
+        if args.is_some() {
+            return Err(Error::new_spanned(
+                attr,
+                "A field can only have one '#[value]' or '#[inject]' attribute.",
+            ));
         }
+        args = Some(parsed);
+    }
+
+    Ok(args)
+}
+
+fn injected_type(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            if segment.ident != "Injected" {
+                return None;
+            }
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                }),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn field_var(field: &Field) -> syn::Ident {
+    format_ident!("__di_{}", field.ident.as_ref().unwrap())
+}
+
+/// Generate a statement that resolves the value of a field into a local variable.
+///
+/// Errors are collected into `errors`, so that every invalid field is reported at once.
+fn resolve_field(field: &Field, expr: TokenStream) -> TokenStream {
+    let ty = &field.ty;
+    resolve_field_result(field, quote! { (|| -> di_rs::Result<#ty> { Ok(#expr) })() })
+}
+
+fn resolve_field_result(field: &Field, result: TokenStream) -> TokenStream {
+    let var = field_var(field);
+    let ty = &field.ty;
+    quote! {
+        let #var: ::std::option::Option<#ty> = match #result {
+            Ok(value) => ::std::option::Option::Some(value),
+            Err(err) => {
+                errors.push(err);
+                ::std::option::Option::None
+            }
+        };
     }
+}
 
-    Ok(None)
+/// Render a type as a string, e.g. `Vec<String>` rather than `Vec < String >`.
+fn type_name(ty: &Type) -> String {
+    let tokens = ty.to_token_stream().to_string();
+    let chars = tokens.chars().collect::<Vec<_>>();
+    let is_word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric() || *c == '_');
+    let mut name = String::new();
+    for (idx, c) in chars.iter().enumerate() {
+        if *c == ' ' && !(is_word(chars.get(idx.wrapping_sub(1))) && is_word(chars.get(idx + 1))) {
+            continue;
+        }
+        name.push(*c);
+        if *c == ',' {
+            name.push(' ');
+        }
+    }
+    name
 }
 
 pub fn generate(input: TokenStream) -> Result<TokenStream> {
     let input: DeriveInput = syn::parse2(input)?;
     let s = match &input.data {
         Data::Struct(s) => s,
-        _ => return Err(Error::new_spanned(input, "It should be a struct")),
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Component can only be derived for structs.",
+            ))
+        }
     };
     let component_args = parse_component_args(&input)?;
-    let interface = &component_args.interface;
+    let interfaces = if component_args.interfaces.is_empty() {
+        vec![quote! { Self }]
+    } else {
+        component_args
+            .interfaces
+            .iter()
+            .map(|path| {
+                if path.is_ident("Self") {
+                    quote! { Self }
+                } else {
+                    quote! { dyn #path }
+                }
+            })
+            .collect()
+    };
     let typename = &input.ident;
+    let mut generics = input.generics.clone();
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    for ident in type_params {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#ident: 'static));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let component_name = component_args.name.unwrap_or_else(|| typename.to_string());
 
-    let mut set_props = Vec::new();
+    let mut resolve_fields = Vec::new();
+    let mut field_values = Vec::new();
+    let mut props_metadata = Vec::new();
     let fields = match &s.fields {
         Fields::Named(fields) => fields,
-        _ => return Err(Error::new_spanned(input, "All fields must be named.")),
+        _ => {
+            return Err(Error::new_spanned(
+                &s.fields,
+                "All fields of a component must be named.",
+            ))
+        }
     };
 
     for field in &fields.named {
         let field_ident = &field.ident;
+        let var = field_var(field);
 
         if let Some(inject_args) = parse_inject_args(&field.attrs)? {
             match inject_args {
-                InjectArgs::InjectValue { name, default } => {
+                InjectArgs::InjectValue {
+                    name,
+                    default,
+                    validators,
+                    flatten,
+                } => {
                     let property_name =
                         name.unwrap_or_else(|| field.ident.as_ref().unwrap().to_string());
+                    let ty = &field.ty;
+                    let required = matches!(default, ValueDefault::None);
                     let get_default = match default {
                         ValueDefault::None => {
                             quote! {
-                                return Err(Error::MissingProperty {
+                                return Err(di_rs::Error::MissingProperty {
                                     component_name: #component_name.to_string(),
                                     property_name: #property_name.to_string(),
                                 });
                             }
                         }
                         ValueDefault::Default => {
-                            quote! { Default::default() }
+                            quote! { ::std::default::Default::default() }
                         }
                         ValueDefault::DefaultCall(fun_name) => {
-                            quote! { #fun_name() }
+                            quote_spanned! { fun_name.span() =>
+                                {
+                                    let default: fn() -> #ty = #fun_name;
+                                    default()
+                                }
+                            }
                         }
-                    };
-
-                    set_props.push(quote! {
-                        #field_ident: match props.get(#property_name) {
-                            Some(value) => {
-                                di_rs::serde_json::from_value(value.clone()).map_err(|err| {
+                        ValueDefault::Literal(lit) => {
+                            quote_spanned! { lit.span() =>
+                                {
+                                    let default: #ty = #lit;
+                                    default
+                                }
+                            }
+                        }
+                        ValueDefault::Str(lit) => {
+                            quote_spanned! { lit.span() =>
+                                {
+                                    let default: #ty = ::std::convert::From::from(#lit);
+                                    default
+                                }
+                            }
+                        }
+                        ValueDefault::Json(lit) => {
+                            quote! {
+                                di_rs::serde_json::from_str(#lit).map_err(|err| {
                                     di_rs::Error::InvalidProperty {
                                         component_name: #component_name.to_string(),
                                         property_name: #property_name.to_string(),
-                                        message: err.to_string(),
+                                        message: format!("invalid default value: {}", err),
                                     }
                                 })?
                             }
-                            None => { #get_default }
+                        }
+                    };
+
+                    let checks = validators.iter().map(|validator| {
+                        let check = validator.check(&quote!(value));
+                        quote! {
+                            if let Err(message) = #check {
+                                return Err(di_rs::Error::InvalidProperty {
+                                    component_name: #component_name.to_string(),
+                                    property_name: #property_name.to_string(),
+                                    message,
+                                });
+                            }
+                        }
+                    });
+                    let validators_metadata = validators.iter().map(Validator::metadata);
+                    let ty_name = type_name(ty);
+
+                    let get_value = if flatten {
+                        quote! {
+                            di_rs::serde_json::from_value(
+                                di_rs::serde_json::Value::Object(props.clone()),
+                            )
+                            .map_err(|err| di_rs::Error::InvalidProperty {
+                                component_name: #component_name.to_string(),
+                                property_name: #property_name.to_string(),
+                                message: err.to_string(),
+                            })?
+                        }
+                    } else {
+                        quote! {
+                            match di_rs::lookup_prop(props, #property_name) {
+                                Some(value) => {
+                                    di_rs::serde_json::from_value(value.clone()).map_err(|err| {
+                                        di_rs::Error::InvalidProperty {
+                                            component_name: #component_name.to_string(),
+                                            property_name: #property_name.to_string(),
+                                            message: err.to_string(),
+                                        }
+                                    })?
+                                }
+                                None => { #get_default }
+                            }
+                        }
+                    };
+                    let kind = if flatten {
+                        quote! { di_rs::PropKind::Flatten }
+                    } else {
+                        quote! { di_rs::PropKind::Value }
+                    };
+
+                    resolve_fields.push(resolve_field(
+                        field,
+                        quote! {{
+                            let value: #ty = #get_value;
+                            #(#checks)*
+                            value
+                        }},
+                    ));
+                    props_metadata.push(quote! {
+                        di_rs::PropMetadata {
+                            name: #property_name.to_string(),
+                            ty: #ty_name.to_string(),
+                            kind: #kind,
+                            required: #required,
+                            validators: vec![#(#validators_metadata),*],
                         }
                     });
                 }
@@ -234,31 +685,30 @@ pub fn generate(input: TokenStream) -> Result<TokenStream> {
                 InjectArgs::InjectComponent { name } => {
                     let property_name =
                         name.unwrap_or_else(|| field.ident.as_ref().unwrap().to_string());
-                    let mut interface_ty = None;
-
-                    match &field.ty {
-                        Type::Path(path)
-                            if path.path.segments.last().unwrap().ident == "Injected" =>
-                        {
-                            if let PathArguments::AngleBracketed(args) =
-                                &path.path.segments.last().unwrap().arguments
-                            {
-                                for arg in &args.args {
-                                    if let GenericArgument::Type(ty) = arg {
-                                        interface_ty = Some(ty);
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
+                    let interface_ty = injected_type(&field.ty).ok_or_else(|| {
+                        Error::new_spanned(
+                            &field.ty,
+                            "Fields marked with '#[inject]' must have the type 'Injected<T>'.",
+                        )
+                    })?;
 
-                    let interface_ty = interface_ty
-                        .ok_or_else(|| Error::new_spanned(&field.ty, "Invalid type"))?;
+                    let ty_name = type_name(interface_ty);
+                    props_metadata.push(quote! {
+                        di_rs::PropMetadata {
+                            name: #property_name.to_string(),
+                            ty: #ty_name.to_string(),
+                            kind: di_rs::PropKind::Inject {
+                                interface: ::std::any::type_name::<#interface_ty>().to_string(),
+                            },
+                            required: true,
+                            validators: Vec::new(),
+                        }
+                    });
 
-                    set_props.push(quote! {
-                        #field_ident: match props.get(#property_name) {
+                    resolve_fields.push(resolve_field(
+                        field,
+                        quote! {
+                            match di_rs::lookup_prop(props, #property_name) {
                             Some(di_rs::serde_json::Value::String(config)) => {
                                 ctx.get::<#interface_ty>(config)?
                             }
@@ -270,37 +720,107 @@ pub fn generate(input: TokenStream) -> Result<TokenStream> {
                                 });
                             }
                             None => {
-                                return Err(Error::MissingProperty {
+                                return Err(di_rs::Error::MissingProperty {
                                     component_name: #component_name.to_string(),
                                     property_name: #property_name.to_string(),
                                 });
                             }
-                        }
-                    });
+                            }
+                        },
+                    ));
+                }
+
+                InjectArgs::ContextValue { name } => {
+                    let value_name =
+                        name.unwrap_or_else(|| field.ident.as_ref().unwrap().to_string());
+                    let value_ty = injected_type(&field.ty).ok_or_else(|| {
+                        Error::new_spanned(
+                            &field.ty,
+                            "Fields marked with '#[inject(from_context)]' must have the type 'Injected<T>'.",
+                        )
+                    })?;
+
+                    resolve_fields.push(resolve_field_result(
+                        field,
+                        quote! { ctx.value::<#value_ty>(#value_name) },
+                    ));
                 }
             }
         } else {
-            set_props.push(quote! {
-                #field_ident: Default::default()
-            });
+            resolve_fields.push(resolve_field(
+                field,
+                quote! { ::std::default::Default::default() },
+            ));
         }
+        field_values.push(quote! { #field_ident: #var.unwrap() });
     }
 
     let component_init = match component_args.init {
-        Some(path) => quote! { #path(&mut component)? },
+        Some(path) => quote! {
+            #path(&mut component)
+                .map_err(|err| di_rs::Error::from(err).with_component(#component_name))?;
+        },
         None => quote! {},
     };
 
+    let component_condition = match component_args.condition {
+        Some(path) => {
+            let condition = quote_spanned! { path.span() => let condition: fn() -> bool = #path; };
+            quote! {
+                fn condition() -> bool {
+                    #condition
+                    condition()
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    let auto_register = if component_args.auto_register {
+        quote! {
+            const _: () = {
+                fn register(registry: &mut di_rs::Registry) -> di_rs::Result<()> {
+                    registry.register_auto::<#typename>()
+                }
+                di_rs::inventory::submit! { di_rs::AutoRegister(register) }
+            };
+        }
+    } else {
+        quote! {}
+    };
+
     let expanded = quote! {
-        #[allow(unused_variables)]
-        impl di_rs::Component for #typename {
-            type Interface = dyn #interface;
+        #auto_register
 
+        #[allow(unused_variables)]
+        impl #impl_generics di_rs::Component for #typename #ty_generics #where_clause {
             fn name() -> &'static str { #component_name }
 
-            fn create(ctx: &mut Context, props: &PropsMap) -> Result<::std::sync::Arc<Self::Interface>> {
+            fn interfaces(interfaces: &mut di_rs::Interfaces<Self>) {
+                #(interfaces.add::<#interfaces>(|component| component);)*
+            }
+
+            #component_condition
+
+            fn props() -> Vec<di_rs::PropMetadata> {
+                vec![#(#props_metadata),*]
+            }
+
+            fn create(ctx: &mut di_rs::Context, props: &di_rs::PropsMap) -> di_rs::Result<::std::sync::Arc<Self>> {
+                let mut errors: Vec<di_rs::Error> = Vec::new();
+                #(#resolve_fields)*
+                match errors.len() {
+                    0 => {}
+                    1 => return Err(errors.remove(0)),
+                    _ => {
+                        return Err(di_rs::Error::InvalidComponent {
+                            component_name: #component_name.to_string(),
+                            errors,
+                        })
+                    }
+                }
                 let mut component = Self {
-                    #(#set_props),*
+                    #(#field_values),*
                 };
                 #component_init
                 Ok(::std::sync::Arc::new(component))
@@ -1,6 +1,7 @@
 extern crate proc_macro;
 
 mod component;
+mod validators;
 
 use proc_macro::TokenStream;
 
@@ -0,0 +1,183 @@
+use crate::component::{expect_name_value, nested_path, parse_path, unknown_attribute};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, Lit, LitStr, Meta, NestedMeta, Path, Result};
+
+const RANGE_ATTRIBUTES: &[&str] = &["min", "max"];
+
+pub enum Validator {
+    Range { min: Option<Lit>, max: Option<Lit> },
+    Regex(LitStr),
+    NonEmpty,
+    OneOf(Vec<Lit>),
+    Custom(Path),
+}
+
+/// Parse a validator from an item of `#[value(...)]`, returns `None` if the item is not a validator.
+pub fn parse(meta: &NestedMeta) -> Result<Option<Validator>> {
+    let path = nested_path(meta)?;
+
+    if path.is_ident("range") {
+        let ls = match meta {
+            NestedMeta::Meta(Meta::List(ls)) => ls,
+            _ => {
+                return Err(Error::new_spanned(
+                    meta,
+                    "Expected 'range(min = ..., max = ...)'.",
+                ))
+            }
+        };
+        let mut min = None;
+        let mut max = None;
+        for meta in &ls.nested {
+            let path = nested_path(meta)?;
+            let (slot, key) = if path.is_ident("min") {
+                (&mut min, "min")
+            } else if path.is_ident("max") {
+                (&mut max, "max")
+            } else {
+                return Err(unknown_attribute(path, "range", RANGE_ATTRIBUTES));
+            };
+            let lit = expect_name_value(meta, key)?;
+            match lit {
+                Lit::Int(_) | Lit::Float(_) => *slot = Some(lit.clone()),
+                _ => {
+                    return Err(Error::new_spanned(
+                        lit,
+                        "The bounds of 'range' should be numbers.",
+                    ))
+                }
+            }
+        }
+        if min.is_none() && max.is_none() {
+            return Err(Error::new_spanned(
+                ls,
+                "Attribute 'range' requires at least one of 'min' or 'max'.",
+            ));
+        }
+        Ok(Some(Validator::Range { min, max }))
+    } else if path.is_ident("regex") {
+        match expect_name_value(meta, "regex")? {
+            Lit::Str(lit) => {
+                if let Err(err) = regex::Regex::new(&lit.value()) {
+                    return Err(Error::new_spanned(
+                        lit,
+                        format!(
+                            "Attribute 'regex' is not a valid regular expression: {}",
+                            err
+                        ),
+                    ));
+                }
+                Ok(Some(Validator::Regex(lit.clone())))
+            }
+            lit => Err(Error::new_spanned(
+                lit,
+                "Attribute 'regex' should be a string.",
+            )),
+        }
+    } else if path.is_ident("non_empty") {
+        match meta {
+            NestedMeta::Meta(Meta::Path(_)) => Ok(Some(Validator::NonEmpty)),
+            _ => Err(Error::new_spanned(
+                meta,
+                "Attribute 'non_empty' does not accept a value.",
+            )),
+        }
+    } else if path.is_ident("one_of") {
+        match meta {
+            NestedMeta::Meta(Meta::List(ls)) => {
+                let mut options = Vec::new();
+                for item in &ls.nested {
+                    match item {
+                        NestedMeta::Lit(lit) => options.push(lit.clone()),
+                        NestedMeta::Meta(meta) => {
+                            return Err(Error::new_spanned(
+                                meta,
+                                "The options of 'one_of' should be literals.",
+                            ))
+                        }
+                    }
+                }
+                Ok(Some(Validator::OneOf(options)))
+            }
+            _ => Err(Error::new_spanned(
+                meta,
+                "Expected 'one_of = [...]' or 'one_of(...)'.",
+            )),
+        }
+    } else if path.is_ident("validate") {
+        let lit = expect_name_value(meta, "validate")?;
+        Ok(Some(Validator::Custom(parse_path(
+            lit,
+            "validate",
+            "validate_fn",
+        )?)))
+    } else {
+        Ok(None)
+    }
+}
+
+fn json_value(lit: &Lit) -> TokenStream {
+    quote! { di_rs::serde_json::json!(#lit) }
+}
+
+fn option_tokens(lit: &Option<Lit>) -> TokenStream {
+    match lit {
+        Some(lit) => quote! { ::std::option::Option::Some(#lit) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+fn option_json(lit: &Option<Lit>) -> TokenStream {
+    match lit {
+        Some(lit) => {
+            let value = json_value(lit);
+            quote! { ::std::option::Option::Some(#value) }
+        }
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+impl Validator {
+    /// Generate an expression of type `Result<(), String>` that validates `value`.
+    pub fn check(&self, value: &TokenStream) -> TokenStream {
+        match self {
+            Validator::Range { min, max } => {
+                let min = option_tokens(min);
+                let max = option_tokens(max);
+                quote! { di_rs::validate::range(&#value, #min, #max) }
+            }
+            Validator::Regex(pattern) => {
+                quote! { di_rs::validate::regex(&#value, #pattern) }
+            }
+            Validator::NonEmpty => quote! { di_rs::validate::non_empty(&#value) },
+            Validator::OneOf(options) => {
+                quote! { di_rs::validate::one_of(&#value, &[#(#options),*]) }
+            }
+            Validator::Custom(path) => quote! { di_rs::validate::custom(#path(&#value)) },
+        }
+    }
+
+    /// Generate the `di_rs::Validator` describing this validator.
+    pub fn metadata(&self) -> TokenStream {
+        match self {
+            Validator::Range { min, max } => {
+                let min = option_json(min);
+                let max = option_json(max);
+                quote! { di_rs::Validator::Range { min: #min, max: #max } }
+            }
+            Validator::Regex(pattern) => {
+                quote! { di_rs::Validator::Regex(#pattern.to_string()) }
+            }
+            Validator::NonEmpty => quote! { di_rs::Validator::NonEmpty },
+            Validator::OneOf(options) => {
+                let options = options.iter().map(json_value);
+                quote! { di_rs::Validator::OneOf(vec![#(#options),*]) }
+            }
+            Validator::Custom(path) => {
+                let name = quote!(#path).to_string().replace(' ', "");
+                quote! { di_rs::Validator::Custom(#name.to_string()) }
+            }
+        }
+    }
+}
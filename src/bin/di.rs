@@ -0,0 +1,172 @@
+use di_rs::{Config, DependencyGraph, Manifest};
+use std::collections::{HashMap, HashSet};
+use std::process;
+
+const USAGE: &str = "\
+Inspect and validate dependency injection configurations.
+
+USAGE:
+    di <COMMAND> --config <FILE> --manifest <FILE> [OPTIONS]
+
+COMMANDS:
+    validate    Check the config and each of its profiles against the manifest
+    graph       Print the dependency graph
+    unused      List the components that are not used
+    cycles      List the dependency cycles
+
+OPTIONS:
+    --config <FILE>      The config file
+    --manifest <FILE>    The manifest exported with `Registry::manifest`
+    --format <FORMAT>    The format of the graph, 'dot' or 'json' [default: dot]
+    --root <KEY>         A config key used by the application, can be repeated
+    --profile <NAME>     The profile to apply to the config
+    --var <NAME=VALUE>   A variable for conditional entries, can be repeated
+    --feature <NAME>     A feature for conditional entries, can be repeated
+";
+
+struct Args {
+    command: String,
+    config: String,
+    manifest: String,
+    format: String,
+    roots: Vec<String>,
+    profile: Option<String>,
+    vars: HashMap<String, String>,
+    features: HashSet<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = std::env::args().skip(1);
+    let command = args.next().ok_or("missing command")?;
+    if command == "-h" || command == "--help" {
+        print!("{}", USAGE);
+        process::exit(0);
+    }
+
+    let mut config = None;
+    let mut manifest = None;
+    let mut format = "dot".to_string();
+    let mut roots = Vec::new();
+    let mut profile = None;
+    let mut vars = HashMap::new();
+    let mut features = HashSet::new();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{}'", arg));
+        match arg.as_str() {
+            "--config" => config = Some(value()?),
+            "--manifest" => manifest = Some(value()?),
+            "--format" => format = value()?,
+            "--root" => roots.push(value()?),
+            "--profile" => profile = Some(value()?),
+            "--var" => {
+                let var = value()?;
+                let (name, value) = var
+                    .split_once('=')
+                    .ok_or(format!("invalid variable '{}', expected NAME=VALUE", var))?;
+                vars.insert(name.to_string(), value.to_string());
+            }
+            "--feature" => {
+                features.insert(value()?);
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Args {
+        command,
+        config: config.ok_or("missing '--config'")?,
+        manifest: manifest.ok_or("missing '--manifest'")?,
+        format,
+        roots,
+        profile,
+        vars,
+        features,
+    })
+}
+
+fn run(args: Args) -> Result<bool, String> {
+    let manifest = Manifest::load(&args.manifest).map_err(|err| err.to_string())?;
+    let mut config = Config::load(&args.config).map_err(|err| err.to_string())?;
+    let profile_problems = match &args.profile {
+        Some(profile) => {
+            config = config.profile(profile).map_err(|err| err.to_string())?;
+            Vec::new()
+        }
+        None => config.validate_profiles(&manifest.components, &args.vars, &args.features),
+    };
+    config.select(|candidate| {
+        candidate
+            .when
+            .as_ref()
+            .is_none_or(|when| when.matches(&args.vars, &args.features))
+    });
+    let graph = DependencyGraph::build(&config, &manifest.components);
+
+    match args.command.as_str() {
+        "validate" => {
+            let mut problems = config.validate(&manifest.components);
+            problems.extend(profile_problems);
+            for problem in &problems {
+                println!("{}", problem);
+            }
+            if problems.is_empty() {
+                println!("ok");
+            }
+            Ok(problems.is_empty())
+        }
+        "graph" => {
+            match args.format.as_str() {
+                "dot" => print!("{}", graph.to_dot()),
+                "json" => println!("{}", graph.to_json()),
+                format => return Err(format!("unknown format '{}'", format)),
+            }
+            Ok(true)
+        }
+        "unused" => {
+            let roots = args.roots.iter().map(String::as_str).collect::<Vec<_>>();
+            for key in graph.unreachable(&roots) {
+                if roots.is_empty() {
+                    println!("config '{}' is not referenced by any component", key);
+                } else {
+                    println!("config '{}' is not used", key);
+                }
+            }
+            for metadata in &manifest.components {
+                if !graph
+                    .nodes
+                    .iter()
+                    .any(|node| node.component == metadata.name)
+                {
+                    println!("component '{}' is not configured", metadata.name);
+                }
+            }
+            Ok(true)
+        }
+        "cycles" => {
+            let cycles = graph.cycles();
+            for cycle in &cycles {
+                println!("{}", cycle.join(" -> "));
+            }
+            Ok(cycles.is_empty())
+        }
+        command => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+    match run(args) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
+    }
+}
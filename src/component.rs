@@ -1,16 +1,77 @@
 use crate::context::Context;
-use crate::Result;
+use crate::{PropMetadata, Result};
+use std::any::{type_name, Any, TypeId};
+use std::marker::PhantomData;
 use std::ops::Deref;
 use std::sync::Arc;
 
 pub type PropsMap = serde_json::Map<String, serde_json::Value>;
 
-pub trait Component {
-    type Interface: 'static + ?Sized;
+/// Look up a property by path.
+///
+/// A path is either a top-level key or a dotted path such as `tls.cert_path`, which
+/// traverses nested objects.
+pub fn lookup_prop<'a>(props: &'a PropsMap, path: &str) -> Option<&'a serde_json::Value> {
+    if let Some(value) = props.get(path) {
+        return Some(value);
+    }
+
+    let mut keys = path.split('.');
+    let mut value = props.get(keys.next()?)?;
+    for key in keys {
+        value = value.as_object()?.get(key)?;
+    }
+    Some(value)
+}
+
+pub(crate) type InterfaceCaster<I> = Box<dyn Fn(&dyn Any) -> Option<Arc<I>> + Send + Sync>;
 
+pub trait Component: Sized + 'static {
     fn name() -> &'static str;
 
-    fn create(ctx: &mut Context, props: &PropsMap) -> Result<Arc<Self::Interface>>;
+    /// Declare the interfaces that this component can be injected as.
+    fn interfaces(interfaces: &mut Interfaces<Self>);
+
+    /// Whether this component can be selected as a candidate of a conditional config entry.
+    fn condition() -> bool {
+        true
+    }
+
+    /// Describe the props read by this component.
+    fn props() -> Vec<PropMetadata> {
+        Vec::new()
+    }
+
+    fn create(ctx: &mut Context, props: &PropsMap) -> Result<Arc<Self>>;
+}
+
+/// The interfaces exposed by a component.
+///
+/// Every interface is a view of the same instance, so a component registered
+/// as both `dyn Storage` and `dyn HealthCheck` is only created once per context.
+pub struct Interfaces<C> {
+    pub(crate) casters: Vec<(TypeId, &'static str, Box<dyn Any + Send + Sync>)>,
+    _mark: PhantomData<fn(C)>,
+}
+
+impl<C: 'static> Interfaces<C> {
+    pub(crate) fn new() -> Self {
+        Interfaces {
+            casters: Vec::new(),
+            _mark: PhantomData,
+        }
+    }
+
+    pub fn add<I: ?Sized + 'static>(&mut self, cast: fn(Arc<C>) -> Arc<I>) -> &mut Self {
+        let caster: InterfaceCaster<I> = Box::new(move |instance| {
+            instance
+                .downcast_ref::<Arc<C>>()
+                .map(|component| cast(component.clone()))
+        });
+        self.casters
+            .push((TypeId::of::<I>(), type_name::<I>(), Box::new(caster)));
+        self
+    }
 }
 
 pub struct Injected<T: ?Sized>(pub(crate) Arc<T>);
@@ -25,6 +86,6 @@ impl<T: ?Sized> Deref for Injected<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        &*self.0
+        &self.0
     }
 }
@@ -1,19 +1,134 @@
-use crate::{PropsMap, Result};
-use serde::Deserialize;
-use std::collections::HashMap;
+use crate::error::suggestions;
+use crate::{lookup_prop, ComponentMetadata, DependencyGraph, Error, PropKind, PropsMap, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 use std::fs;
 use std::path::Path;
 
-#[derive(Deserialize, Default)]
+/// The condition of a conditional config entry, all requirements must hold.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Condition {
+    /// Required values of the variables set with `SystemBuilder::var`.
+    #[serde(default)]
+    pub vars: HashMap<String, String>,
+    /// Environment variables that must be set.
+    #[serde(default)]
+    pub env: Vec<String>,
+    /// Features that must be enabled with `SystemBuilder::feature`.
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl Condition {
+    pub fn matches(&self, vars: &HashMap<String, String>, features: &HashSet<String>) -> bool {
+        self.vars
+            .iter()
+            .all(|(name, value)| vars.get(name) == Some(value))
+            && self.env.iter().all(|name| std::env::var_os(name).is_some())
+            && self
+                .features
+                .iter()
+                .all(|feature| features.contains(feature))
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
 pub struct ComponentConfig {
     pub name: String,
     #[serde(default)]
     pub props: PropsMap,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
 }
 
 #[derive(Deserialize, Default)]
+#[serde(try_from = "Value")]
 pub struct Config {
     pub components: HashMap<String, ComponentConfig>,
+    /// The candidates of conditional entries, in order of preference.
+    ///
+    /// A key is conditional if its entry has a `when` condition or is a list of candidates.
+    pub variants: HashMap<String, Vec<ComponentConfig>>,
+    /// Named patches of this config, applied with `Config::profile`.
+    pub profiles: HashMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct RawConfig {
+    components: HashMap<String, RawEntry>,
+    #[serde(default)]
+    profiles: HashMap<String, Value>,
+}
+
+enum RawEntry {
+    Single(ComponentConfig),
+    Variants(Vec<ComponentConfig>),
+}
+
+impl<'de> Deserialize<'de> for RawEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error;
+
+        match serde_json::Value::deserialize(deserializer)? {
+            value @ serde_json::Value::Array(_) => serde_json::from_value(value)
+                .map(RawEntry::Variants)
+                .map_err(D::Error::custom),
+            value => serde_json::from_value(value)
+                .map(RawEntry::Single)
+                .map_err(D::Error::custom),
+        }
+    }
+}
+
+impl TryFrom<Value> for Config {
+    type Error = serde_json::Error;
+
+    fn try_from(value: Value) -> std::result::Result<Self, Self::Error> {
+        let raw = RawConfig::deserialize(&value)?;
+        let mut config = Config {
+            profiles: raw.profiles,
+            ..Config::default()
+        };
+        for (key, entry) in raw.components {
+            match entry {
+                RawEntry::Single(component) if component.when.is_none() => {
+                    config.components.insert(key, component);
+                }
+                RawEntry::Single(component) => {
+                    config.variants.insert(key, vec![component]);
+                }
+                RawEntry::Variants(candidates) => {
+                    config.variants.insert(key, candidates);
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Apply a JSON merge patch (RFC 7386) to `target`.
+fn merge_patch(target: &mut Value, patch: &Value) {
+    let patch = match patch {
+        Value::Object(patch) => patch,
+        patch => {
+            *target = patch.clone();
+            return;
+        }
+    };
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+    let target = target.as_object_mut().unwrap();
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
 }
 
 impl Config {
@@ -22,4 +137,144 @@ impl Config {
             fs::OpenOptions::new().read(true).open(path)?,
         )?)
     }
+
+    /// Returns this config patched by profile `name`.
+    ///
+    /// A profile is a JSON merge patch of the config file, e.g. `{ "components": { "cache": null } }`
+    /// removes the `cache` entry. It is applied to the current entries of this config, and the
+    /// returned config keeps the profiles.
+    pub fn profile(&self, name: &str) -> Result<Config> {
+        let patch = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| Error::ProfileNotFound {
+                name: name.to_string(),
+                suggestions: suggestions(name, self.profiles.keys().map(String::as_str)),
+            })?;
+        let mut value = self.entries();
+        merge_patch(&mut value, patch);
+        let mut config = Config::try_from(value)?;
+        config.profiles = self.profiles.clone();
+        Ok(config)
+    }
+
+    /// Returns the entries of this config in the JSON form of a config file.
+    fn entries(&self) -> Value {
+        let mut components = serde_json::Map::new();
+        for (key, component) in &self.components {
+            components.insert(key.clone(), serde_json::to_value(component).unwrap());
+        }
+        for (key, candidates) in &self.variants {
+            let value = match candidates.as_slice() {
+                [candidate] if candidate.when.is_some() => serde_json::to_value(candidate),
+                candidates => serde_json::to_value(candidates),
+            };
+            components.insert(key.clone(), value.unwrap());
+        }
+        serde_json::json!({ "components": components })
+    }
+
+    /// Validate every profile of this config, see `Config::validate`.
+    ///
+    /// The conditional entries of each profile are resolved with `vars` and `features` first.
+    /// Problems are prefixed with the name of the profile.
+    pub fn validate_profiles<'a>(
+        &self,
+        components: impl IntoIterator<Item = &'a ComponentMetadata>,
+        vars: &HashMap<String, String>,
+        features: &HashSet<String>,
+    ) -> Vec<String> {
+        let components = components.into_iter().collect::<Vec<_>>();
+        let mut names = self.profiles.keys().collect::<Vec<_>>();
+        names.sort();
+
+        let mut problems = Vec::new();
+        for name in names {
+            let mut config = match self.profile(name) {
+                Ok(config) => config,
+                Err(err) => {
+                    problems.push(format!("profile '{}': {}", name, err));
+                    continue;
+                }
+            };
+            config.select(|candidate| {
+                candidate
+                    .when
+                    .as_ref()
+                    .is_none_or(|when| when.matches(vars, features))
+            });
+            problems.extend(
+                config
+                    .validate(components.iter().copied())
+                    .into_iter()
+                    .map(|problem| format!("profile '{}': {}", name, problem)),
+            );
+        }
+        problems
+    }
+
+    /// Resolve the conditional entries.
+    ///
+    /// Every conditional key is set to its first candidate accepted by `enabled`, or removed if
+    /// there is none.
+    pub fn select(&mut self, mut enabled: impl FnMut(&ComponentConfig) -> bool) {
+        for (key, candidates) in self.variants.drain() {
+            if let Some(component) = candidates.into_iter().find(|candidate| enabled(candidate)) {
+                self.components.insert(key, component);
+            }
+        }
+    }
+
+    /// Check this config against the metadata of the registered components.
+    ///
+    /// Returns a description of every problem found: unregistered components, missing
+    /// required props, dependencies that cannot be resolved and dependency cycles.
+    pub fn validate<'a>(
+        &self,
+        components: impl IntoIterator<Item = &'a ComponentMetadata>,
+    ) -> Vec<String> {
+        let components = components.into_iter().collect::<Vec<_>>();
+        let graph = DependencyGraph::build(self, components.iter().copied());
+        let mut problems = Vec::new();
+
+        for node in &graph.nodes {
+            let metadata = match components
+                .iter()
+                .find(|metadata| metadata.name == node.component)
+            {
+                Some(metadata) => metadata,
+                None => {
+                    problems.push(format!(
+                        "{}: component '{}' is not registered",
+                        node.key, node.component
+                    ));
+                    continue;
+                }
+            };
+            let props = &self.components[&node.key].props;
+            for prop in &metadata.props {
+                if prop.required
+                    && prop.kind != PropKind::Flatten
+                    && lookup_prop(props, &prop.name).is_none()
+                {
+                    problems.push(format!("{}: missing property '{}'", node.key, prop.name));
+                }
+            }
+        }
+
+        for edge in graph.unresolved_edges() {
+            problems.push(format!(
+                "{}: property '{}': {}",
+                edge.from,
+                edge.prop,
+                edge.unresolved.as_deref().unwrap_or_default()
+            ));
+        }
+
+        for cycle in graph.cycles() {
+            problems.push(format!("circular dependency: {}", cycle.join(" -> ")));
+        }
+
+        problems
+    }
 }
@@ -1,56 +1,535 @@
-use crate::config::Config;
-use crate::{Error, Injected, Registry, Result};
+use crate::component::InterfaceCaster;
+use crate::error::suggestions;
+use crate::hooks::{Hooks, ResolutionEvent};
+use crate::{
+    ComponentMetadata, Config, CreationRecord, Error, Injected, PropKind, PropsMap, Result, System,
+};
 use std::any::{type_name, Any, TypeId};
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub(crate) struct Instance {
+    pub(crate) object: Box<dyn Any>,
+    pub(crate) metadata: Arc<ComponentMetadata>,
+}
+
+/// A value seeded into a scope with `Context::insert`.
+pub(crate) struct Value {
+    pub(crate) object: Box<dyn Any>,
+    pub(crate) type_name: &'static str,
+}
+
+/// A component created in a scope, in creation order.
+struct Created {
+    key: String,
+    type_name: String,
+    interface: &'static str,
+    at: Instant,
+}
+
+/// The instances owned by a context, chained to the scope of its parent context.
+pub(crate) struct Scope {
+    pub(crate) system: System,
+    pub(crate) parent: Option<Rc<Scope>>,
+    pub(crate) config: Config,
+    pub(crate) overrides: RefCell<HashMap<String, Instance>>,
+    pub(crate) instances: RefCell<HashMap<String, Instance>>,
+    pub(crate) values: RefCell<HashMap<String, Value>>,
+    /// Decorated instances by config key and interface.
+    pub(crate) decorated: RefCell<HashMap<(String, TypeId), Box<dyn Any>>>,
+    created: RefCell<Vec<Created>>,
+}
+
+impl Scope {
+    pub(crate) fn new(system: System, parent: Option<Rc<Scope>>, config: Config) -> Self {
+        Scope {
+            system,
+            parent,
+            config,
+            overrides: Default::default(),
+            instances: Default::default(),
+            values: Default::default(),
+            decorated: Default::default(),
+            created: Default::default(),
+        }
+    }
+
+    /// Returns this scope followed by its ancestors.
+    fn chain(&self) -> impl Iterator<Item = &Scope> {
+        std::iter::successors(Some(self), |scope| scope.parent.as_deref())
+    }
+}
+
+/// The timing of a component being created.
+#[derive(Default)]
+pub(crate) struct Timing {
+    dependencies: Duration,
+    depth: usize,
+    /// The nearest scope, as a position in the scope chain, owning a dependency or context value.
+    nearest: Cell<Option<usize>>,
+}
+
+impl Timing {
+    fn uses(&self, position: usize) {
+        let nearest = self
+            .nearest
+            .get()
+            .map_or(position, |nearest| nearest.min(position));
+        self.nearest.set(Some(nearest));
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let hooks = &self.system.inner.hooks.on_dispose;
+        let instances = self.instances.get_mut();
+        self.decorated.get_mut().clear();
+        for created in self.created.get_mut().drain(..).rev() {
+            Hooks::emit(
+                hooks,
+                &ResolutionEvent {
+                    key: &created.key,
+                    type_name: &created.type_name,
+                    interface: created.interface,
+                    elapsed: created.at.elapsed(),
+                    error: None,
+                },
+            );
+            instances.remove(&created.key);
+        }
+    }
+}
 
 pub struct Context {
-    pub(crate) config: Arc<Config>,
-    pub(crate) registry: Arc<Registry>,
-    pub(crate) instances: HashMap<String, Box<dyn Any>>,
+    pub(crate) system: System,
+    pub(crate) scope: Rc<Scope>,
+    pub(crate) stack: Vec<String>,
+    pub(crate) timings: Vec<Timing>,
 }
 
 impl Context {
-    fn downcast_component<T: Any + ?Sized>(
-        instance: &Box<dyn Any>,
-        component_name: &str,
-    ) -> Result<Injected<T>> {
-        match instance.downcast_ref::<Injected<T>>() {
-            Some(injected) => Ok(injected.clone()),
+    /// Create a child context.
+    ///
+    /// The child can use every instance already resolved by this context and its ancestors.
+    /// An instance created by the child is cached in the scope whose config defines its key,
+    /// the root for keys of the system config, unless it depends on instances or values of a
+    /// nearer scope, and is dropped together with that scope.
+    pub fn child(&self) -> Context {
+        self.child_with_config(Config::default())
+    }
+
+    /// Create a child context with extra config entries that are only visible to the child.
+    ///
+    /// Entries in `config` shadow entries with the same key in the ancestors and the system config.
+    pub fn child_with_config(&self, mut config: Config) -> Context {
+        self.system.inner.select(&mut config);
+        Context {
+            system: self.system.clone(),
+            scope: Rc::new(Scope::new(
+                self.system.clone(),
+                Some(self.scope.clone()),
+                config,
+            )),
+            stack: Vec::new(),
+            timings: Vec::new(),
+        }
+    }
+
+    /// Seed a runtime value, such as the current user or a request id, into this context.
+    ///
+    /// Components created in this context or its children can inject it with `#[inject(from_context)]`.
+    pub fn insert<T: Any>(&mut self, name: impl Into<String>, value: T) -> &mut Self {
+        self.scope.values.borrow_mut().insert(
+            name.into(),
+            Value {
+                object: Box::new(Arc::new(value)),
+                type_name: type_name::<T>(),
+            },
+        );
+        self
+    }
+
+    /// Returns a value seeded into this context or one of its ancestors.
+    pub fn value<T: Any>(&self, name: &str) -> Result<Injected<T>> {
+        for (position, scope) in self.scope.chain().enumerate() {
+            if let Some(value) = scope.values.borrow().get(name) {
+                self.uses_scope(position);
+                return match value.object.downcast_ref::<Arc<T>>() {
+                    Some(value) => Ok(Injected(value.clone())),
+                    None => Err(Error::ContextValueTypeMismatch {
+                        name: name.to_string(),
+                        type_name: type_name::<T>().to_string(),
+                        value_type_name: value.type_name.to_string(),
+                    }),
+                };
+            }
+        }
+        Err(Error::ContextValueNotFound {
+            name: name.to_string(),
+            type_name: type_name::<T>().to_string(),
+        })
+    }
+
+    /// Record that the component being created depends on the scope at `position` in the chain.
+    fn uses_scope(&self, position: usize) {
+        if let Some(timing) = self.timings.last() {
+            timing.uses(position);
+        }
+    }
+
+    pub fn get<T: Any + ?Sized>(&mut self, name: &str) -> Result<Injected<T>> {
+        let system = self.system.clone();
+        let scope = self.scope.clone();
+
+        for (position, scope) in scope.chain().enumerate() {
+            if let Some(instance) = scope.overrides.borrow().get(name) {
+                self.uses_scope(position);
+                system
+                    .inner
+                    .resolved
+                    .lock()
+                    .unwrap()
+                    .insert(name.to_string());
+                return match instance.object.downcast_ref::<Arc<T>>() {
+                    Some(instance) => Ok(Injected(instance.clone())),
+                    None => Err(Error::NotImplemented {
+                        trait_name: type_name::<T>().to_string(),
+                        component_name: instance.metadata.name.clone(),
+                        interfaces: instance.metadata.interfaces.clone(),
+                    }),
+                };
+            }
+        }
+
+        let config = system.config();
+        let registry = system.registry();
+        let component_config = scope
+            .chain()
+            .find_map(|scope| scope.config.components.get(name))
+            .or_else(|| config.components.get(name))
+            .ok_or_else(|| Error::ComponentConfigNotFound {
+                name: name.to_string(),
+                suggestions: suggestions(
+                    name,
+                    scope
+                        .chain()
+                        .flat_map(|scope| scope.config.components.keys())
+                        .chain(config.components.keys())
+                        .map(String::as_str),
+                ),
+            })?;
+
+        let entry = match registry
+            .types
+            .get(&TypeId::of::<T>())
+            .and_then(|components| components.get(component_config.name.as_str()))
+        {
+            Some(entry) => entry,
+            None => {
+                let metadata = registry.find(&component_config.name);
+                let err = match metadata {
+                    Some(metadata) => Error::NotImplemented {
+                        trait_name: type_name::<T>().to_string(),
+                        component_name: component_config.name.clone(),
+                        interfaces: metadata.interfaces.clone(),
+                    },
+                    None => Error::ComponentNotFound {
+                        component_name: component_config.name.clone(),
+                        trait_name: type_name::<T>().to_string(),
+                        suggestions: suggestions(
+                            &component_config.name,
+                            registry
+                                .components()
+                                .into_iter()
+                                .map(|metadata| metadata.name.as_str()),
+                        ),
+                    },
+                };
+                Hooks::emit(
+                    &system.inner.hooks.on_error,
+                    &ResolutionEvent {
+                        key: name,
+                        type_name: metadata
+                            .map_or(&component_config.name, |metadata| &metadata.type_name),
+                        interface: type_name::<T>(),
+                        elapsed: Duration::ZERO,
+                        error: Some(&err),
+                    },
+                );
+                return Err(err);
+            }
+        };
+        let caster = entry
+            .caster
+            .downcast_ref::<InterfaceCaster<T>>()
+            .expect("invalid interface caster");
+        let cast = |instance: &Instance| match caster(instance.object.as_ref()) {
+            Some(instance) => Ok(Injected(instance)),
             None => Err(Error::NotImplemented {
                 trait_name: type_name::<T>().to_string(),
-                component_name: component_name.to_string(),
+                component_name: component_config.name.clone(),
+                interfaces: instance.metadata.interfaces.clone(),
             }),
+        };
+
+        // A key defined in the config of a scope is never shared with the ancestors of that scope,
+        // while a key of the system config belongs to the root scope.
+        let chain = scope.chain().collect::<Vec<_>>();
+        let defined = chain
+            .iter()
+            .position(|scope| scope.config.components.contains_key(name))
+            .unwrap_or(chain.len() - 1);
+        let mut cached = None;
+        for (position, scope) in chain[..=defined].iter().enumerate() {
+            if let Some(instance) = scope.instances.borrow().get(name) {
+                cached = Some((cast(instance)?, position));
+                break;
+            }
         }
-    }
 
-    pub fn get<T: Any + ?Sized>(&mut self, name: &str) -> Result<Injected<T>> {
-        match self.config.clone().components.get(name) {
-            Some(component_config) => {
-                if let Some(instance) = self.instances.get(name) {
-                    return Self::downcast_component::<T>(instance, &component_config.name);
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "di.get",
+            key = name,
+            component = component_config.name.as_str(),
+            interface = type_name::<T>(),
+            cache = if cached.is_some() { "hit" } else { "miss" },
+            elapsed_us = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing")]
+        let _enter = span.enter();
+
+        let (injected, position) = match cached {
+            Some(cached) => cached,
+            None => {
+                if self.stack.iter().any(|key| key == name) {
+                    let mut path = self.stack.clone();
+                    path.push(name.to_string());
+                    return Err(Error::CircularDependency { path });
+                }
+
+                let started = Instant::now();
+                self.stack.push(name.to_string());
+                self.timings.push(Timing::default());
+                let res = if system.inner.strict {
+                    check_unknown_props(&entry.metadata, &component_config.props)
+                } else {
+                    Ok(())
+                };
+                let res = res.and_then(|_| (entry.create)(self, &component_config.props));
+                let res = res.map_err(|err| err.with_path(&self.stack));
+                self.stack.pop();
+                let timing = self.timings.pop().unwrap_or_default();
+                let elapsed = started.elapsed();
+                if let Some(parent) = self.timings.last_mut() {
+                    parent.dependencies += elapsed;
+                    parent.depth = parent.depth.max(timing.depth + 1);
                 }
+                #[cfg(feature = "tracing")]
+                span.record("elapsed_us", elapsed.as_micros() as u64);
 
-                let components = self.registry.types.get(&TypeId::of::<T>()).ok_or_else(|| {
-                    Error::TraitNotDefined {
-                        name: type_name::<T>().to_string(),
+                let hooks = &system.inner.hooks;
+                let event = |error| ResolutionEvent {
+                    key: name,
+                    type_name: &entry.metadata.type_name,
+                    interface: type_name::<T>(),
+                    elapsed,
+                    error,
+                };
+                let object = match res {
+                    Ok(object) => object,
+                    Err(err) => {
+                        Hooks::emit(&hooks.on_error, &event(Some(&err)));
+                        return Err(err);
                     }
-                })?;
-                match components.get(component_config.name.as_str()) {
-                    Some(factory) => {
-                        let instance = factory(self, &component_config.props)?;
-                        self.instances.insert(name.to_string(), instance);
-                        let instance = self.instances.get(name).unwrap();
-                        Self::downcast_component::<T>(instance, &component_config.name)
+                };
+                Hooks::emit(&hooks.on_create, &event(None));
+
+                let instance = Instance {
+                    object,
+                    metadata: entry.metadata.clone(),
+                };
+                system
+                    .inner
+                    .resolved
+                    .lock()
+                    .unwrap()
+                    .insert(name.to_string());
+                // The instance is cached in the scope owning its key, unless it depends on
+                // instances or values of a nearer scope, which it must not outlive.
+                let position = timing
+                    .nearest
+                    .get()
+                    .map_or(defined, |nearest| nearest.min(defined));
+                let owner = chain[position];
+
+                // Only the first creation of each key owned by a root scope is recorded, which
+                // bounds the records by the keys of the system config.
+                if owner.parent.is_none() {
+                    let mut creations = system.inner.creations.lock().unwrap();
+                    if !creations.iter().any(|record| record.key == name) {
+                        creations.push(CreationRecord {
+                            key: name.to_string(),
+                            component: component_config.name.clone(),
+                            interface: type_name::<T>().to_string(),
+                            elapsed,
+                            self_elapsed: elapsed.saturating_sub(timing.dependencies),
+                            depth: timing.depth + 1,
+                        });
                     }
-                    None => Err(Error::ComponentNotFound {
-                        name: name.to_string(),
-                    }),
                 }
+
+                let injected = cast(&instance)?;
+                owner
+                    .instances
+                    .borrow_mut()
+                    .insert(name.to_string(), instance);
+                owner.created.borrow_mut().push(Created {
+                    key: name.to_string(),
+                    type_name: entry.metadata.type_name.clone(),
+                    interface: type_name::<T>(),
+                    at: Instant::now(),
+                });
+                (injected, position)
             }
-            None => Err(Error::ComponentConfigNotFound {
-                name: name.to_string(),
-            }),
+        };
+        self.uses_scope(position);
+
+        let decorators = registry.decorators::<T>(name);
+        if decorators.is_empty() {
+            return Ok(injected);
         }
+
+        // Like instances, the decorated instance is cached in the owner of the undecorated one,
+        // unless the decorators depend on instances or values of a nearer scope.
+        let decorated_key = (name.to_string(), TypeId::of::<T>());
+        for (position, scope) in chain[..=position].iter().enumerate() {
+            if let Some(decorated) = scope.decorated.borrow().get(&decorated_key) {
+                self.uses_scope(position);
+                return Ok(Injected(
+                    decorated.downcast_ref::<Arc<T>>().unwrap().clone(),
+                ));
+            }
+        }
+
+        if self.stack.iter().any(|key| key == name) {
+            let mut path = self.stack.clone();
+            path.push(name.to_string());
+            return Err(Error::CircularDependency { path });
+        }
+
+        let started = Instant::now();
+        self.stack.push(name.to_string());
+        self.timings.push(Timing::default());
+        let mut res = Ok(injected.0);
+        for decorator in decorators {
+            res = res.and_then(|instance| (decorator.decorate)(instance, self));
+        }
+        let res = res.map_err(|err| err.with_path(&self.stack));
+        self.stack.pop();
+        let timing = self.timings.pop().unwrap_or_default();
+        if let Some(parent) = self.timings.last_mut() {
+            parent.dependencies += timing.dependencies;
+            parent.depth = parent.depth.max(timing.depth);
+        }
+        let instance = match res {
+            Ok(instance) => instance,
+            Err(err) => {
+                Hooks::emit(
+                    &system.inner.hooks.on_error,
+                    &ResolutionEvent {
+                        key: name,
+                        type_name: &entry.metadata.type_name,
+                        interface: type_name::<T>(),
+                        elapsed: started.elapsed(),
+                        error: Some(&err),
+                    },
+                );
+                return Err(err);
+            }
+        };
+
+        let position = timing
+            .nearest
+            .get()
+            .map_or(position, |nearest| nearest.min(position));
+        chain[position]
+            .decorated
+            .borrow_mut()
+            .insert(decorated_key, Box::new(instance.clone()));
+        self.uses_scope(position);
+        Ok(Injected(instance))
+    }
+}
+
+/// Pushes an error for every key of `props` at `prefix` that is not on the path of a prop name.
+///
+/// `names` are the remaining segments of the dotted prop names under `prefix`.
+fn find_unknown_props(
+    metadata: &ComponentMetadata,
+    props: &PropsMap,
+    prefix: &str,
+    names: &[Vec<&str>],
+    errors: &mut Vec<Error>,
+) {
+    let known = || names.iter().map(|name| name[0]);
+    for (key, value) in props {
+        let path = format!("{}{}", prefix, key);
+        if !known().any(|name| name == key.as_str()) {
+            errors.push(Error::UnknownProperty {
+                component_name: metadata.name.clone(),
+                suggestions: suggestions(key, known())
+                    .into_iter()
+                    .map(|name| format!("{}{}", prefix, name))
+                    .collect(),
+                property_name: path,
+            });
+            continue;
+        }
+
+        // A prop declared with this exact name reads the whole value.
+        let nested = names
+            .iter()
+            .filter(|name| name[0] == key.as_str())
+            .map(|name| name[1..].to_vec())
+            .collect::<Vec<_>>();
+        if nested.iter().any(Vec::is_empty) {
+            continue;
+        }
+        if let Some(value) = value.as_object() {
+            find_unknown_props(metadata, value, &format!("{}.", path), &nested, errors);
+        }
+    }
+}
+
+/// Returns an error for every prop that is not read by the component.
+fn check_unknown_props(metadata: &ComponentMetadata, props: &PropsMap) -> Result<()> {
+    if metadata.props_unknown
+        || metadata
+            .props
+            .iter()
+            .any(|prop| prop.kind == PropKind::Flatten)
+    {
+        return Ok(());
+    }
+
+    let names = metadata
+        .props
+        .iter()
+        .map(|prop| prop.name.split('.').collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut errors = Vec::new();
+    find_unknown_props(metadata, props, "", &names, &mut errors);
+
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.into_iter().next().unwrap()),
+        _ => Err(Error::InvalidComponent {
+            component_name: metadata.name.clone(),
+            errors,
+        }),
     }
 }
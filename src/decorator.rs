@@ -0,0 +1,45 @@
+use crate::{Context, Result};
+use std::sync::Arc;
+
+pub(crate) type DecorateFn<I> = Arc<dyn Fn(Arc<I>, &mut Context) -> Result<Arc<I>> + Send + Sync>;
+
+/// Wraps the components resolved as interface `I`, e.g. to add logging or retries to a `dyn Storage`.
+///
+/// Decorators are applied by `Context::get` after the component is created, in ascending order,
+/// so the decorator with the highest order is the outermost one.
+pub struct Decorator<I: ?Sized> {
+    pub(crate) keys: Vec<String>,
+    pub(crate) order: i32,
+    pub(crate) decorate: DecorateFn<I>,
+}
+
+impl<I: ?Sized + 'static> Decorator<I> {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(Arc<I>, &mut Context) -> Result<Arc<I>> + Send + Sync + 'static,
+    {
+        Decorator {
+            keys: Vec::new(),
+            order: 0,
+            decorate: Arc::new(f),
+        }
+    }
+
+    /// Only decorate the component configured as `key`, can be called several times.
+    ///
+    /// Without keys, every component resolved as `I` is decorated.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into());
+        self
+    }
+
+    /// Set the order of this decorator, decorators with the same order are applied in registration order.
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub(crate) fn applies_to(&self, key: &str) -> bool {
+        self.keys.is_empty() || self.keys.iter().any(|k| k == key)
+    }
+}
@@ -13,23 +13,47 @@ pub enum Error {
     #[error("System not running")]
     SystemNotRunning,
 
-    #[error("Configure '{name}' not found")]
-    ConfigureNotFound { name: String },
-
-    #[error("Trait '{name}' is not defined")]
-    TraitNotDefined { name: String },
-
-    #[error("Component '{name}' not found")]
-    ComponentNotFound { name: String },
+    #[error(
+        "Component '{component_name}' is not registered for '{trait_name}'{}",
+        display_suggestions(.suggestions)
+    )]
+    ComponentNotFound {
+        component_name: String,
+        trait_name: String,
+        suggestions: Vec<String>,
+    },
 
-    #[error("Component '{component_name}' not implemented for '{trait_name}'")]
+    #[error(
+        "Component '{component_name}' not implemented for '{trait_name}', it is registered for {}",
+        display_names(.interfaces)
+    )]
     NotImplemented {
         trait_name: String,
         component_name: String,
+        interfaces: Vec<String>,
     },
 
-    #[error("Component config '{name}' not found")]
-    ComponentConfigNotFound { name: String },
+    #[error("Component config '{name}' not found{}", display_suggestions(.suggestions))]
+    ComponentConfigNotFound {
+        name: String,
+        suggestions: Vec<String>,
+    },
+
+    #[error("Profile '{name}' not found{}", display_suggestions(.suggestions))]
+    ProfileNotFound {
+        name: String,
+        suggestions: Vec<String>,
+    },
+
+    #[error("Context value '{name}' of type '{type_name}' not found")]
+    ContextValueNotFound { name: String, type_name: String },
+
+    #[error("Context value '{name}' is of type '{value_type_name}', not '{type_name}'")]
+    ContextValueTypeMismatch {
+        name: String,
+        type_name: String,
+        value_type_name: String,
+    },
 
     #[error("Component '{component_name}' missing propery '{property_name}'")]
     MissingProperty {
@@ -37,6 +61,29 @@ pub enum Error {
         property_name: String,
     },
 
+    #[error(
+        "Component '{component_name}' is already registered by '{existing_type_name}', \
+         use `register_override` to replace it with '{type_name}'"
+    )]
+    DuplicateComponent {
+        component_name: String,
+        type_name: String,
+        existing_type_name: String,
+    },
+
+    #[error("Registration failed: {}", display_errors(.errors))]
+    Registration { errors: Vec<Error> },
+
+    #[error(
+        "Component '{component_name}' does not have property '{property_name}'{}",
+        display_suggestions(.suggestions)
+    )]
+    UnknownProperty {
+        component_name: String,
+        property_name: String,
+        suggestions: Vec<String>,
+    },
+
     #[error("Invalid property '{property_name}' for component '{component_name}': {message}")]
     InvalidProperty {
         component_name: String,
@@ -44,8 +91,101 @@ pub enum Error {
         message: String,
     },
 
-    #[error("Other error: {0}")]
-    Other(anyhow::Error),
+    #[error("Component '{component_name}' is invalid: {}", display_errors(.errors))]
+    InvalidComponent {
+        component_name: String,
+        errors: Vec<Error>,
+    },
+
+    #[error("Circular dependency: {}", .path.join(" -> "))]
+    CircularDependency { path: Vec<String> },
+
+    #[error("{}: {error}", .path.join(" -> "))]
+    Resolve {
+        path: Vec<String>,
+        error: Box<Error>,
+    },
+
+    #[error("Other error: {0:#}")]
+    Other(#[from] anyhow::Error),
+}
+
+impl Error {
+    /// Combines registration errors into a single error.
+    pub(crate) fn from_errors(mut errors: Vec<Error>) -> Result<()> {
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(Error::Registration { errors }),
+        }
+    }
+
+    /// Attach the resolution path of the component that failed to be created.
+    pub(crate) fn with_path(self, path: &[String]) -> Self {
+        match self {
+            Error::Resolve { .. } | Error::CircularDependency { .. } => self,
+            err => Error::Resolve {
+                path: path.to_vec(),
+                error: Box::new(err),
+            },
+        }
+    }
+
+    /// Attach the component name to an error returned by an `init` hook.
+    #[doc(hidden)]
+    pub fn with_component(self, component_name: &str) -> Self {
+        match self {
+            Error::Other(err) => Error::Other(err.context(format!(
+                "Failed to initialize component '{}'",
+                component_name
+            ))),
+            err => err,
+        }
+    }
+}
+
+fn display_names(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| format!("'{}'", name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn display_suggestions(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(", did you mean {}?", display_names(suggestions))
+    }
+}
+
+/// Returns the candidates that are close to `name`, the closest first.
+pub(crate) fn suggestions<'a>(
+    name: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> Vec<String> {
+    let max_distance = (name.len() / 3).max(2);
+    let mut suggestions = candidates
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (strsim::levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect::<Vec<_>>();
+    suggestions.sort();
+    suggestions.dedup();
+    suggestions
+        .into_iter()
+        .take(3)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+fn display_errors(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 pub type Result<T> = ::std::result::Result<T, Error>;
@@ -0,0 +1,235 @@
+use crate::config::Config;
+use crate::{lookup_prop, ComponentMetadata, PropKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// A component of the dependency graph, one per config entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// The config key.
+    pub key: String,
+
+    /// The component name referenced by the config entry.
+    pub component: String,
+
+    /// The Rust type of the component, if it is registered.
+    pub type_name: Option<String>,
+
+    /// The interfaces exposed by the component.
+    pub interfaces: Vec<String>,
+}
+
+/// A dependency declared by an `#[inject]` prop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    /// The config key of the dependent component.
+    pub from: String,
+
+    /// The config key of the dependency.
+    pub to: String,
+
+    /// The prop that references the dependency.
+    pub prop: String,
+
+    /// The interface the dependency is injected as.
+    pub interface: String,
+
+    /// Why the dependency cannot be resolved, `None` if it can.
+    pub unresolved: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+    /// Build the dependency graph of a config from the metadata of the registered components.
+    pub fn build<'a>(
+        config: &Config,
+        components: impl IntoIterator<Item = &'a ComponentMetadata>,
+    ) -> DependencyGraph {
+        let components = components.into_iter().collect::<Vec<_>>();
+        let find_component = |name: &str| components.iter().find(|metadata| metadata.name == name);
+
+        let mut keys = config.components.keys().collect::<Vec<_>>();
+        keys.sort();
+
+        let mut graph = DependencyGraph::default();
+        for key in keys {
+            let component_config = &config.components[key];
+            let metadata = find_component(&component_config.name);
+
+            graph.nodes.push(GraphNode {
+                key: key.clone(),
+                component: component_config.name.clone(),
+                type_name: metadata.map(|metadata| metadata.type_name.clone()),
+                interfaces: metadata
+                    .map(|metadata| metadata.interfaces.clone())
+                    .unwrap_or_default(),
+            });
+
+            let metadata = match metadata {
+                Some(metadata) => metadata,
+                None => continue,
+            };
+            for prop in &metadata.props {
+                let interface = match &prop.kind {
+                    PropKind::Inject { interface } => interface,
+                    _ => continue,
+                };
+                let to = match lookup_prop(&component_config.props, &prop.name) {
+                    Some(serde_json::Value::String(to)) => to,
+                    _ => continue,
+                };
+                let unresolved = match config.components.get(to) {
+                    None => Some(format!("config '{}' not found", to)),
+                    Some(target) => match find_component(&target.name) {
+                        None => Some(format!("component '{}' is not registered", target.name)),
+                        Some(target) if !target.interfaces.contains(interface) => Some(format!(
+                            "component '{}' does not implement '{}'",
+                            target.name, interface
+                        )),
+                        Some(_) => None,
+                    },
+                };
+                graph.edges.push(GraphEdge {
+                    from: key.clone(),
+                    to: to.clone(),
+                    prop: prop.name.clone(),
+                    interface: interface.clone(),
+                    unresolved,
+                });
+            }
+        }
+        graph
+    }
+
+    /// Returns the edges that cannot be resolved.
+    pub fn unresolved_edges(&self) -> impl Iterator<Item = &GraphEdge> {
+        self.edges.iter().filter(|edge| edge.unresolved.is_some())
+    }
+
+    /// Returns the dependency cycles, each one as a path of config keys.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        fn visit<'a>(
+            graph: &'a DependencyGraph,
+            key: &'a str,
+            stack: &mut Vec<&'a str>,
+            visited: &mut HashSet<&'a str>,
+            cycles: &mut Vec<Vec<String>>,
+        ) {
+            if let Some(idx) = stack.iter().position(|item| *item == key) {
+                let mut cycle = stack[idx..]
+                    .iter()
+                    .map(|key| key.to_string())
+                    .collect::<Vec<_>>();
+                cycle.push(key.to_string());
+                cycles.push(cycle);
+                return;
+            }
+            if !visited.insert(key) {
+                return;
+            }
+            stack.push(key);
+            for edge in graph.edges.iter().filter(|edge| edge.from == key) {
+                visit(graph, &edge.to, stack, visited, cycles);
+            }
+            stack.pop();
+        }
+
+        let mut cycles = Vec::new();
+        let mut visited = HashSet::new();
+        for node in &self.nodes {
+            visit(self, &node.key, &mut Vec::new(), &mut visited, &mut cycles);
+        }
+        cycles
+    }
+
+    /// Returns the config keys that cannot be reached from `roots`.
+    ///
+    /// If `roots` is empty, returns the config keys that no other component depends on.
+    pub fn unreachable(&self, roots: &[&str]) -> Vec<&str> {
+        if roots.is_empty() {
+            return self
+                .nodes
+                .iter()
+                .filter(|node| !self.edges.iter().any(|edge| edge.to == node.key))
+                .map(|node| node.key.as_str())
+                .collect();
+        }
+
+        let mut reachable = HashSet::new();
+        let mut pending = roots.to_vec();
+        while let Some(key) = pending.pop() {
+            if reachable.insert(key) {
+                pending.extend(
+                    self.edges
+                        .iter()
+                        .filter(|edge| edge.from == key)
+                        .map(|edge| edge.to.as_str()),
+                );
+            }
+        }
+        self.nodes
+            .iter()
+            .filter(|node| !reachable.contains(node.key.as_str()))
+            .map(|node| node.key.as_str())
+            .collect()
+    }
+
+    /// Render the graph in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph components {{").unwrap();
+        writeln!(dot, "    node [shape=box];").unwrap();
+        for node in &self.nodes {
+            let mut label = format!("{}\\n{}", escape(&node.key), escape(&node.component));
+            for interface in &node.interfaces {
+                write!(label, "\\n<{}>", escape(interface)).unwrap();
+            }
+            let style = if node.type_name.is_none() {
+                ", color=red"
+            } else {
+                ""
+            };
+            writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\"{}];",
+                escape(&node.key),
+                label,
+                style
+            )
+            .unwrap();
+        }
+        for edge in &self.edges {
+            let style = if edge.unresolved.is_some() {
+                ", color=red, style=dashed"
+            } else {
+                ""
+            };
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [label=\"{}\"{}];",
+                escape(&edge.from),
+                escape(&edge.to),
+                escape(&edge.prop),
+                style
+            )
+            .unwrap();
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    /// Render the graph as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,34 @@
+use crate::Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// An event passed to the hooks registered with `SystemBuilder`.
+pub struct ResolutionEvent<'a> {
+    /// The config key of the component.
+    pub key: &'a str,
+    /// The type name of the component, or its name for `on_error` if it is not registered.
+    pub type_name: &'a str,
+    /// The type name of the interface the component was resolved as.
+    pub interface: &'a str,
+    /// The creation time for `on_create` and `on_error`, the lifetime of the instance for `on_dispose`.
+    pub elapsed: Duration,
+    /// The error for `on_error`.
+    pub error: Option<&'a Error>,
+}
+
+pub(crate) type Hook = Arc<dyn Fn(&ResolutionEvent) + Send + Sync>;
+
+#[derive(Default)]
+pub(crate) struct Hooks {
+    pub(crate) on_create: Vec<Hook>,
+    pub(crate) on_error: Vec<Hook>,
+    pub(crate) on_dispose: Vec<Hook>,
+}
+
+impl Hooks {
+    pub(crate) fn emit(hooks: &[Hook], event: &ResolutionEvent) {
+        for hook in hooks {
+            hook(event);
+        }
+    }
+}
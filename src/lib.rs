@@ -1,16 +1,36 @@
 mod component;
 mod config;
 mod context;
+mod decorator;
 mod error;
+mod graph;
+mod hooks;
+mod metadata;
 mod registry;
+mod report;
+mod secret;
 mod system;
+mod testing;
+pub mod validate;
 
+#[doc(hidden)]
+pub use inventory;
 #[doc(hidden)]
 pub use serde_json;
 
-pub use component::{Component, Injected, PropsMap};
+pub use component::{lookup_prop, Component, Injected, Interfaces, PropsMap};
+pub use config::{ComponentConfig, Condition, Config};
 pub use context::Context;
+pub use decorator::Decorator;
+pub use di_derive::Component;
 pub use error::{Error, Result};
+pub use graph::{DependencyGraph, GraphEdge, GraphNode};
+pub use hooks::ResolutionEvent;
+pub use metadata::{ComponentMetadata, Manifest, PropKind, PropMetadata, Validator};
+#[doc(hidden)]
+pub use registry::AutoRegister;
 pub use registry::Registry;
-pub use di_derive::Component;
-pub use system::{create_context, SystemBuilder};
+pub use report::{CreationRecord, StartupReport};
+pub use secret::Secret;
+pub use system::{create_context, system, System, SystemBuilder, PROFILE_ENV};
+pub use testing::TestContainer;
@@ -0,0 +1,78 @@
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// The metadata of all the components of a registry.
+///
+/// An application can export its manifest, so that its configurations can be checked
+/// by the `di` command-line tool without starting the application.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub components: Vec<ComponentMetadata>,
+}
+
+impl Manifest {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(serde_json::from_reader(
+            fs::OpenOptions::new().read(true).open(path)?,
+        )?)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Describes a registered component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentMetadata {
+    pub name: String,
+    pub type_name: String,
+    pub interfaces: Vec<String>,
+    pub props: Vec<PropMetadata>,
+    /// Whether the component reads props that are not described by `props`,
+    /// as components registered with a closure do.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub props_unknown: bool,
+}
+
+/// Describes a property read by a component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropMetadata {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(flatten)]
+    pub kind: PropKind,
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub validators: Vec<Validator>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PropKind {
+    /// A value deserialized from the props, declared with `#[value]`.
+    Value,
+
+    /// A struct deserialized from all the props, declared with `#[value(flatten)]`.
+    Flatten,
+
+    /// A reference to another component, declared with `#[inject]`.
+    Inject { interface: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Validator {
+    Range {
+        min: Option<Value>,
+        max: Option<Value>,
+    },
+    Regex(String),
+    NonEmpty,
+    OneOf(Vec<Value>),
+    Custom(String),
+}
@@ -1,29 +1,285 @@
-use crate::component::{Component, Injected, PropsMap};
+use crate::component::{Component, InterfaceCaster, Interfaces, PropsMap};
 use crate::context::Context;
-use crate::Result;
-use std::any::{Any, TypeId};
+use crate::{ComponentMetadata, Decorator, Error, Manifest, Result};
+use std::any::{type_name, Any, TypeId};
 use std::collections::HashMap;
+use std::sync::Arc;
 
-type ComponentCreator = fn(&mut Context, &PropsMap) -> Result<Box<dyn Any>>;
+/// A `Decorator<I>` and its order.
+type DecoratorEntry = (i32, Box<dyn Any + Send + Sync>);
+
+type ComponentCreator = Arc<dyn Fn(&mut Context, &PropsMap) -> Result<Box<dyn Any>> + Send + Sync>;
+
+pub(crate) struct ComponentEntry {
+    pub(crate) create: ComponentCreator,
+    pub(crate) caster: Box<dyn Any + Send + Sync>,
+    pub(crate) condition: fn() -> bool,
+    pub(crate) metadata: Arc<ComponentMetadata>,
+}
+
+/// A component submitted with `#[di(auto_register)]`.
+#[doc(hidden)]
+pub struct AutoRegister(pub fn(&mut Registry) -> Result<()>);
+
+inventory::collect!(AutoRegister);
 
 #[derive(Default)]
 pub struct Registry {
-    pub(crate) types: HashMap<TypeId, HashMap<&'static str, ComponentCreator>>,
+    pub(crate) types: HashMap<TypeId, HashMap<&'static str, ComponentEntry>>,
+    /// The decorators of each interface, sorted by order.
+    pub(crate) decorators: HashMap<TypeId, Vec<DecoratorEntry>>,
 }
 
 impl Registry {
-    pub fn register<T: Component>(&mut self) {
-        let component_name = T::name();
-        let f: ComponentCreator = |ctx, props| Ok(Box::new(Injected(T::create(ctx, props)?)));
-        self.types
-            .entry(TypeId::of::<T::Interface>())
-            .and_modify(|components| {
-                components.insert(component_name, f);
-            })
-            .or_insert_with(|| {
-                let mut components = HashMap::new();
-                components.insert(component_name, f);
-                components
+    /// Register a component, failing if another component is already registered with the same name.
+    pub fn register<T: Component>(&mut self) -> Result<()> {
+        self.register_as::<T>(T::name())
+    }
+
+    /// Register a component under `component_name` instead of `Component::name`.
+    ///
+    /// This allows several instantiations of a generic component to be registered side by side.
+    pub fn register_as<T: Component>(&mut self, component_name: &'static str) -> Result<()> {
+        self.check_duplicate(component_name, type_name::<T>())?;
+        self.insert::<T>(component_name);
+        Ok(())
+    }
+
+    /// Register a pre-built instance as component `component_name` implementing `I`.
+    ///
+    /// Every context resolves the component to the same instance.
+    pub fn register_instance<I: ?Sized + Send + Sync + 'static>(
+        &mut self,
+        component_name: &'static str,
+        instance: Arc<I>,
+    ) -> Result<()> {
+        self.register_fn::<I, _>(component_name, move |_, _| Ok(instance.clone()))
+    }
+
+    /// Register a closure that creates component `component_name` implementing `I` from its props.
+    pub fn register_fn<I, F>(&mut self, component_name: &'static str, f: F) -> Result<()>
+    where
+        I: ?Sized + 'static,
+        F: Fn(&mut Context, &PropsMap) -> Result<Arc<I>> + Send + Sync + 'static,
+    {
+        self.check_duplicate(component_name, type_name::<I>())?;
+
+        let caster: InterfaceCaster<I> =
+            Box::new(|instance| instance.downcast_ref::<Arc<I>>().cloned());
+        self.insert_entry(
+            component_name,
+            ComponentMetadata {
+                name: component_name.to_string(),
+                type_name: type_name::<I>().to_string(),
+                interfaces: vec![type_name::<I>().to_string()],
+                props: Vec::new(),
+                props_unknown: true,
+            },
+            Arc::new(move |ctx, props| Ok(Box::new(f(ctx, props)?))),
+            || true,
+            vec![(TypeId::of::<I>(), Box::new(caster))],
+        );
+        Ok(())
+    }
+
+    /// Register all components marked with `#[di(auto_register)]`, including those of dependent crates.
+    ///
+    /// Components that are already registered with the same type are skipped.
+    pub fn register_all(&mut self) -> Result<()> {
+        let errors = inventory::iter::<AutoRegister>
+            .into_iter()
+            .filter_map(|AutoRegister(register)| register(self).err())
+            .collect();
+        Error::from_errors(errors)
+    }
+
+    /// Register a component for `register_all`, skipping it if it is already registered with the same type.
+    #[doc(hidden)]
+    pub fn register_auto<T: Component>(&mut self) -> Result<()> {
+        if self
+            .find(T::name())
+            .is_some_and(|existing| existing.type_name == type_name::<T>())
+        {
+            return Ok(());
+        }
+        self.register::<T>()
+    }
+
+    /// Register a component, replacing any component already registered with the same name.
+    ///
+    /// This is intended for intentional replacements such as test doubles.
+    pub fn register_override<T: Component>(&mut self) {
+        self.register_override_as::<T>(T::name());
+    }
+
+    /// Register a component under `component_name`, replacing any component already registered with that name.
+    pub fn register_override_as<T: Component>(&mut self, component_name: &'static str) {
+        self.remove(component_name);
+        self.insert::<T>(component_name);
+    }
+
+    /// Move all components of `other` into this registry.
+    ///
+    /// Fails without modifying this registry if both registries contain a component with the same name.
+    pub fn merge(&mut self, other: Registry) -> Result<()> {
+        if let Some(metadata) = other
+            .components()
+            .into_iter()
+            .find(|metadata| self.find(&metadata.name).is_some())
+        {
+            return Err(Error::DuplicateComponent {
+                component_name: metadata.name.clone(),
+                type_name: metadata.type_name.clone(),
+                existing_type_name: self.find(&metadata.name).unwrap().type_name.clone(),
             });
+        }
+
+        for (type_id, components) in other.types {
+            self.types.entry(type_id).or_default().extend(components);
+        }
+        for (type_id, decorators) in other.decorators {
+            let entry = self.decorators.entry(type_id).or_default();
+            entry.extend(decorators);
+            entry.sort_by_key(|(order, _)| *order);
+        }
+        Ok(())
+    }
+
+    /// Decorate every component resolved as `I`.
+    pub fn register_decorator<I, F>(&mut self, f: F)
+    where
+        I: ?Sized + 'static,
+        F: Fn(Arc<I>, &mut Context) -> Result<Arc<I>> + Send + Sync + 'static,
+    {
+        self.add_decorator(Decorator::new(f));
+    }
+
+    /// Add a decorator, restricted to config keys or ordered with `Decorator::key` and `Decorator::order`.
+    pub fn add_decorator<I: ?Sized + 'static>(&mut self, decorator: Decorator<I>) {
+        let decorators = self.decorators.entry(TypeId::of::<I>()).or_default();
+        decorators.push((decorator.order, Box::new(decorator)));
+        decorators.sort_by_key(|(order, _)| *order);
+    }
+
+    /// Returns the decorators of interface `I` that apply to config key `key`, in order.
+    pub(crate) fn decorators<I: ?Sized + 'static>(&self, key: &str) -> Vec<&Decorator<I>> {
+        self.decorators
+            .get(&TypeId::of::<I>())
+            .into_iter()
+            .flatten()
+            .filter_map(|(_, decorator)| decorator.downcast_ref::<Decorator<I>>())
+            .filter(|decorator| decorator.applies_to(key))
+            .collect()
+    }
+
+    fn check_duplicate(&self, component_name: &str, type_name: &str) -> Result<()> {
+        match self.find(component_name) {
+            Some(existing) => Err(Error::DuplicateComponent {
+                component_name: component_name.to_string(),
+                type_name: type_name.to_string(),
+                existing_type_name: existing.type_name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn remove(&mut self, component_name: &str) {
+        for components in self.types.values_mut() {
+            components.remove(component_name);
+        }
+        self.types.retain(|_, components| !components.is_empty());
+    }
+
+    fn insert<T: Component>(&mut self, component_name: &'static str) {
+        let mut interfaces = Interfaces::<T>::new();
+        T::interfaces(&mut interfaces);
+
+        self.insert_entry(
+            component_name,
+            ComponentMetadata {
+                name: component_name.to_string(),
+                type_name: type_name::<T>().to_string(),
+                interfaces: interfaces
+                    .casters
+                    .iter()
+                    .map(|(_, interface_name, _)| interface_name.to_string())
+                    .collect(),
+                props: T::props(),
+                props_unknown: false,
+            },
+            Arc::new(|ctx, props| Ok(Box::new(T::create(ctx, props)?))),
+            T::condition,
+            interfaces
+                .casters
+                .into_iter()
+                .map(|(type_id, _, caster)| (type_id, caster))
+                .collect(),
+        );
+    }
+
+    fn insert_entry(
+        &mut self,
+        component_name: &'static str,
+        metadata: ComponentMetadata,
+        create: ComponentCreator,
+        condition: fn() -> bool,
+        casters: Vec<(TypeId, Box<dyn Any + Send + Sync>)>,
+    ) {
+        let metadata = Arc::new(metadata);
+        for (type_id, caster) in casters {
+            self.types.entry(type_id).or_default().insert(
+                component_name,
+                ComponentEntry {
+                    create: create.clone(),
+                    caster,
+                    condition,
+                    metadata: metadata.clone(),
+                },
+            );
+        }
+    }
+
+    /// Returns whether the condition of the component registered as `name` holds.
+    ///
+    /// Unregistered components are considered enabled, so that they are reported when resolved.
+    pub(crate) fn is_enabled(&self, name: &str) -> bool {
+        self.types
+            .values()
+            .find_map(|components| components.get(name))
+            .is_none_or(|entry| (entry.condition)())
+    }
+
+    /// Returns the metadata of the component registered as `name` for any interface.
+    pub fn find(&self, name: &str) -> Option<&ComponentMetadata> {
+        self.types
+            .values()
+            .find_map(|components| components.get(name))
+            .map(|entry| entry.metadata.as_ref())
+    }
+
+    /// Returns the metadata of all registered components, ordered by name.
+    pub fn components(&self) -> Vec<&ComponentMetadata> {
+        let mut components: Vec<&ComponentMetadata> = Vec::new();
+        for entry in self
+            .types
+            .values()
+            .flat_map(|components| components.values())
+        {
+            if !components
+                .iter()
+                .any(|metadata| std::ptr::eq(*metadata, entry.metadata.as_ref()))
+            {
+                components.push(&entry.metadata);
+            }
+        }
+        components.sort_by(|a, b| a.name.cmp(&b.name));
+        components
+    }
+
+    /// Returns the manifest of this registry.
+    pub fn manifest(&self) -> Manifest {
+        Manifest {
+            components: self.components().into_iter().cloned().collect(),
+        }
     }
 }
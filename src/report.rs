@@ -0,0 +1,67 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+/// The creation of a component.
+#[derive(Debug, Clone)]
+pub struct CreationRecord {
+    pub key: String,
+    pub component: String,
+    pub interface: String,
+    /// The time spent creating the component, including its dependencies.
+    pub elapsed: Duration,
+    /// The time spent creating the component, excluding its dependencies.
+    pub self_elapsed: Duration,
+    /// The length of the longest chain of dependencies created for the component, including itself.
+    pub depth: usize,
+}
+
+/// A summary of the components created so far, see `System::startup_report`.
+#[derive(Debug, Clone, Default)]
+pub struct StartupReport {
+    /// The created components, slowest first by `self_elapsed`.
+    pub components: Vec<CreationRecord>,
+}
+
+impl StartupReport {
+    /// The total time spent creating components.
+    pub fn total(&self) -> Duration {
+        self.components
+            .iter()
+            .map(|record| record.self_elapsed)
+            .sum()
+    }
+
+    /// The maximum dependency depth.
+    pub fn depth(&self) -> usize {
+        self.components
+            .iter()
+            .map(|record| record.depth)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl Display for StartupReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} components created in {:?}, maximum depth {}",
+            self.components.len(),
+            self.total(),
+            self.depth()
+        )?;
+        for record in &self.components {
+            writeln!(
+                f,
+                "{:>12?} {:>12?} {:>3}  {} ({} as {})",
+                record.self_elapsed,
+                record.elapsed,
+                record.depth,
+                record.key,
+                record.component,
+                record.interface
+            )?;
+        }
+        Ok(())
+    }
+}
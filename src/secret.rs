@@ -0,0 +1,91 @@
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::fmt::{self, Debug, Formatter};
+use std::fs;
+use zeroize::Zeroize;
+
+/// A property value that must not be leaked.
+///
+/// A secret can be written inline in the props, or read from an environment variable or a file:
+///
+/// ```json
+/// { "password": { "env": "DB_PASS" } }
+/// { "password": { "file": "/run/secrets/db" } }
+/// ```
+///
+/// The value is redacted from `Debug` output and error messages, and is zeroized on drop.
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    /// Returns the secret value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize + Clone> Clone for Secret<T> {
+    fn clone(&self) -> Self {
+        Secret(self.0.clone())
+    }
+}
+
+impl<T: Zeroize> Debug for Secret<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(***)")
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+fn read_source(value: &Value) -> Result<Option<String>, String> {
+    let object = match value.as_object() {
+        Some(object) if object.len() == 1 => object,
+        _ => return Ok(None),
+    };
+
+    if let Some(name) = object.get("env").and_then(Value::as_str) {
+        std::env::var(name)
+            .map(Some)
+            .map_err(|_| format!("environment variable '{}' is not set", name))
+    } else if let Some(path) = object.get("file").and_then(Value::as_str) {
+        let mut content = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read secret file '{}': {}", path, err))?;
+        let value = content.trim_end_matches(&['\r', '\n'][..]).to_string();
+        content.zeroize();
+        Ok(Some(value))
+    } else {
+        Ok(None)
+    }
+}
+
+impl<'de, T: Zeroize + DeserializeOwned> Deserialize<'de> for Secret<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let value = match read_source(&value).map_err(D::Error::custom)? {
+            Some(mut content) => {
+                let value = Value::String(content.clone());
+                content.zeroize();
+                value
+            }
+            None => value,
+        };
+
+        // The error of the inner type may contain the value, so it is not forwarded.
+        match serde_json::from_value(value) {
+            Ok(value) => Ok(Secret(value)),
+            Err(_) => Err(D::Error::custom("invalid secret value")),
+        }
+    }
+}
@@ -1,28 +1,125 @@
 use crate::config::Config;
-use crate::context::Context;
-use crate::{Component, Error, Registry};
+use crate::context::{Context, Scope};
+use crate::hooks::{Hooks, ResolutionEvent};
+use crate::{
+    Component, CreationRecord, Decorator, DependencyGraph, Error, PropsMap, Registry, Result,
+    StartupReport,
+};
 use once_cell::sync::OnceCell;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 static SYSTEM: OnceCell<System> = OnceCell::new();
 
-struct System {
-    registry: Arc<Registry>,
-    config: Arc<Config>,
+/// The environment variable naming the profile to activate.
+pub const PROFILE_ENV: &str = "DI_PROFILE";
+
+pub(crate) struct SystemInner {
+    pub(crate) registry: Registry,
+    pub(crate) config: Config,
+    pub(crate) strict: bool,
+    pub(crate) vars: HashMap<String, String>,
+    pub(crate) features: HashSet<String>,
+    pub(crate) resolved: Mutex<HashSet<String>>,
+    pub(crate) creations: Mutex<Vec<CreationRecord>>,
+    pub(crate) hooks: Hooks,
+}
+
+impl SystemInner {
+    /// Resolve the conditional entries of `config` with the variables and features of this system.
+    pub(crate) fn select(&self, config: &mut Config) {
+        select(config, &self.registry, &self.vars, &self.features);
+    }
+}
+
+fn select(
+    config: &mut Config,
+    registry: &Registry,
+    vars: &HashMap<String, String>,
+    features: &HashSet<String>,
+) {
+    config.select(|candidate| {
+        candidate
+            .when
+            .as_ref()
+            .is_none_or(|when| when.matches(vars, features))
+            && registry.is_enabled(&candidate.name)
+    });
+}
+
+#[derive(Clone)]
+pub struct System {
+    pub(crate) inner: Arc<SystemInner>,
+}
+
+impl System {
+    pub fn config(&self) -> &Config {
+        &self.inner.config
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.inner.registry
+    }
+
+    /// Returns the dependency graph of the configured components.
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        DependencyGraph::build(&self.inner.config, self.inner.registry.components())
+    }
+
+    /// Returns the config keys that have not been resolved by any context so far.
+    ///
+    /// Calling this after startup reports the configured components that are never used.
+    pub fn unreferenced_components(&self) -> Vec<String> {
+        let resolved = self.inner.resolved.lock().unwrap();
+        let mut keys = self
+            .inner
+            .config
+            .components
+            .keys()
+            .filter(|key| !resolved.contains(*key))
+            .cloned()
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    /// Summarize the creation times and dependency depths of the components created so far.
+    ///
+    /// Only the first creation of each key owned by a root context is included, so components
+    /// created per request in child contexts do not make the report grow.
+    pub fn startup_report(&self) -> StartupReport {
+        let mut components = self.inner.creations.lock().unwrap().clone();
+        components.sort_by_key(|record| std::cmp::Reverse(record.self_elapsed));
+        StartupReport { components }
+    }
+
+    pub fn create_context(&self) -> Context {
+        Context {
+            system: self.clone(),
+            scope: Rc::new(Scope::new(self.clone(), None, Config::default())),
+            timings: Vec::new(),
+            stack: Default::default(),
+        }
+    }
 }
 
+#[derive(Default)]
 pub struct SystemBuilder {
     config_file: Option<PathBuf>,
     registry: Registry,
+    strict: bool,
+    vars: HashMap<String, String>,
+    features: HashSet<String>,
+    profile: Option<String>,
+    hooks: Hooks,
+    errors: Vec<Error>,
 }
 
 impl SystemBuilder {
     pub fn new() -> SystemBuilder {
-        SystemBuilder {
-            config_file: None,
-            registry: Default::default(),
-        }
+        Default::default()
     }
 
     pub fn config_file(mut self, path: impl AsRef<Path>) -> Self {
@@ -31,38 +128,185 @@ impl SystemBuilder {
     }
 
     pub fn register<C: Component>(mut self) -> Self {
-        self.registry.register::<C>();
+        if let Err(err) = self.registry.register::<C>() {
+            self.errors.push(err);
+        }
         self
     }
 
-    pub fn run<F, R>(self, f: F) -> R
+    pub fn register_as<C: Component>(mut self, name: &'static str) -> Self {
+        if let Err(err) = self.registry.register_as::<C>(name) {
+            self.errors.push(err);
+        }
+        self
+    }
+
+    /// Register a pre-built instance as component `name` implementing `I`.
+    pub fn register_instance<I: ?Sized + Send + Sync + 'static>(
+        mut self,
+        name: &'static str,
+        instance: Arc<I>,
+    ) -> Self {
+        if let Err(err) = self.registry.register_instance(name, instance) {
+            self.errors.push(err);
+        }
+        self
+    }
+
+    /// Register a closure that creates component `name` implementing `I`.
+    pub fn register_fn<I, F>(mut self, name: &'static str, f: F) -> Self
     where
-        F: FnOnce() -> R,
+        I: ?Sized + 'static,
+        F: Fn(&mut Context, &PropsMap) -> Result<Arc<I>> + Send + Sync + 'static,
     {
-        let config = match self.config_file {
-            Some(path) => Config::load(path).expect("Failed to load config file"),
+        if let Err(err) = self.registry.register_fn(name, f) {
+            self.errors.push(err);
+        }
+        self
+    }
+
+    /// Decorate every component resolved as `I`.
+    pub fn register_decorator<I, F>(mut self, f: F) -> Self
+    where
+        I: ?Sized + 'static,
+        F: Fn(Arc<I>, &mut Context) -> Result<Arc<I>> + Send + Sync + 'static,
+    {
+        self.registry.register_decorator(f);
+        self
+    }
+
+    /// Add a decorator, restricted to config keys or ordered with `Decorator::key` and `Decorator::order`.
+    pub fn add_decorator<I: ?Sized + 'static>(mut self, decorator: Decorator<I>) -> Self {
+        self.registry.add_decorator(decorator);
+        self
+    }
+
+    /// Call `f` after a component is created.
+    pub fn on_create<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&ResolutionEvent) + Send + Sync + 'static,
+    {
+        self.hooks.on_create.push(Arc::new(f));
+        self
+    }
+
+    /// Call `f` when resolving a configured component fails, because it is not registered,
+    /// does not implement the requested interface, or fails to be created or decorated.
+    pub fn on_error<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&ResolutionEvent) + Send + Sync + 'static,
+    {
+        self.hooks.on_error.push(Arc::new(f));
+        self
+    }
+
+    /// Call `f` before a component is dropped with the context that created it.
+    pub fn on_dispose<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&ResolutionEvent) + Send + Sync + 'static,
+    {
+        self.hooks.on_dispose.push(Arc::new(f));
+        self
+    }
+
+    /// Register all components marked with `#[di(auto_register)]`.
+    pub fn register_all(mut self) -> Self {
+        match self.registry.register_all() {
+            Ok(()) => {}
+            Err(Error::Registration { errors }) => self.errors.extend(errors),
+            Err(err) => self.errors.push(err),
+        }
+        self
+    }
+
+    /// Register a component, replacing any component already registered with the same name.
+    pub fn register_override<C: Component>(mut self) -> Self {
+        self.registry.register_override::<C>();
+        self
+    }
+
+    /// Add all components of a registry, e.g. one exported by another crate.
+    pub fn registry(mut self, registry: Registry) -> Self {
+        if let Err(err) = self.registry.merge(registry) {
+            self.errors.push(err);
+        }
+        self
+    }
+
+    /// In strict mode, creating a component fails if its config contains props that it does not read.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Activate a profile of the config file.
+    ///
+    /// Without it, the profile named by the `DI_PROFILE` environment variable is activated, if set.
+    pub fn profile(mut self, name: impl Into<String>) -> Self {
+        self.profile = Some(name.into());
+        self
+    }
+
+    /// Set a variable for the `vars` conditions of conditional config entries.
+    pub fn var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    /// Enable a feature for the `features` conditions of conditional config entries.
+    ///
+    /// Pass cargo features of the application explicitly, e.g. `if cfg!(feature = "redis")`.
+    pub fn feature(mut self, name: impl Into<String>) -> Self {
+        self.features.insert(name.into());
+        self
+    }
+
+    /// Build the system without installing it as the running system.
+    ///
+    /// Fails if the config file cannot be loaded or any registration failed.
+    pub fn build(self) -> Result<System> {
+        Error::from_errors(self.errors)?;
+
+        let mut config = match self.config_file {
+            Some(path) => Config::load(path)?,
             None => Default::default(),
         };
+        if let Some(profile) = self.profile.or_else(|| std::env::var(PROFILE_ENV).ok()) {
+            config = config.profile(&profile)?;
+        }
+        select(&mut config, &self.registry, &self.vars, &self.features);
+
+        Ok(System {
+            inner: Arc::new(SystemInner {
+                registry: self.registry,
+                config,
+                strict: self.strict,
+                vars: self.vars,
+                features: self.features,
+                resolved: Default::default(),
+                creations: Default::default(),
+                hooks: self.hooks,
+            }),
+        })
+    }
 
+    pub fn run<F, R>(self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
         SYSTEM
-            .set(System {
-                config: Arc::new(config),
-                registry: Arc::new(self.registry),
-            })
+            .set(self.build().expect("Failed to build system"))
             .map_err(|_| "Failed to initialize system")
             .unwrap();
         f()
     }
 }
 
-fn get_system() -> &'static System {
-    SYSTEM.get().ok_or_else(|| Error::SystemNotRunning).unwrap()
+/// Returns the running system.
+pub fn system() -> &'static System {
+    SYSTEM.get().ok_or(Error::SystemNotRunning).unwrap()
 }
 
 pub fn create_context() -> Context {
-    Context {
-        config: get_system().config.clone(),
-        registry: get_system().registry.clone(),
-        instances: Default::default(),
-    }
+    system().create_context()
 }
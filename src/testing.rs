@@ -0,0 +1,69 @@
+use crate::context::Instance;
+use crate::{ComponentMetadata, Context, Injected, Result, System, SystemBuilder};
+use std::any::{type_name, Any};
+use std::sync::Arc;
+
+/// A container for unit tests that does not install a global system.
+///
+/// It uses the production config and registrations, but selected config keys can be
+/// replaced with mock instances.
+pub struct TestContainer {
+    system: System,
+    ctx: Context,
+}
+
+impl TestContainer {
+    /// Build a container from a system builder.
+    ///
+    /// Panics if the system fails to build.
+    pub fn new(builder: SystemBuilder) -> Self {
+        let system = builder.build().expect("Failed to build system");
+        let ctx = system.create_context();
+        TestContainer { system, ctx }
+    }
+
+    /// Resolve config key `name` to `instance` instead of creating the configured component.
+    pub fn override_instance<I: ?Sized + 'static>(self, name: &str, instance: Arc<I>) -> Self {
+        self.ctx.scope.overrides.borrow_mut().insert(
+            name.to_string(),
+            Instance {
+                object: Box::new(instance),
+                metadata: Arc::new(ComponentMetadata {
+                    name: type_name::<I>().to_string(),
+                    type_name: type_name::<I>().to_string(),
+                    interfaces: vec![type_name::<I>().to_string()],
+                    props: Vec::new(),
+                    props_unknown: false,
+                }),
+            },
+        );
+        self
+    }
+
+    pub fn get<T: Any + ?Sized>(&mut self, name: &str) -> Result<Injected<T>> {
+        self.ctx.get(name)
+    }
+
+    pub fn context(&mut self) -> &mut Context {
+        &mut self.ctx
+    }
+
+    pub fn system(&self) -> &System {
+        &self.system
+    }
+
+    /// Returns the config keys resolved so far, including overridden ones, ordered by key.
+    pub fn resolved(&self) -> Vec<String> {
+        let mut keys = self
+            .system
+            .inner
+            .resolved
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+}
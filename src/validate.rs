@@ -0,0 +1,121 @@
+//! Validators used by the `#[value(...)]` attribute.
+//!
+//! Each validator returns a message describing the problem, which is reported as
+//! `Error::InvalidProperty`.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{Debug, Display};
+use std::sync::Mutex;
+
+pub type ValidateResult = std::result::Result<(), String>;
+
+pub fn range<T: PartialOrd + Display>(value: &T, min: Option<T>, max: Option<T>) -> ValidateResult {
+    if let Some(min) = min {
+        if *value < min {
+            return Err(format!("must be at least {}, got {}", min, value));
+        }
+    }
+    if let Some(max) = max {
+        if *value > max {
+            return Err(format!("must be at most {}, got {}", max, value));
+        }
+    }
+    Ok(())
+}
+
+pub fn regex<T: AsRef<str> + ?Sized>(value: &T, pattern: &'static str) -> ValidateResult {
+    static CACHE: Lazy<Mutex<HashMap<&'static str, Regex>>> = Lazy::new(Default::default);
+
+    let mut cache = CACHE.lock().unwrap();
+    let re = match cache.get(pattern) {
+        Some(re) => re,
+        None => {
+            let re = Regex::new(pattern).map_err(|err| err.to_string())?;
+            cache.entry(pattern).or_insert(re)
+        }
+    };
+    if re.is_match(value.as_ref()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{}' does not match the pattern '{}'",
+            value.as_ref(),
+            pattern
+        ))
+    }
+}
+
+pub fn non_empty<T: IsEmpty + ?Sized>(value: &T) -> ValidateResult {
+    if value.is_empty() {
+        Err("must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+pub fn one_of<T: PartialEq<U> + Debug, U: Debug>(value: &T, options: &[U]) -> ValidateResult {
+    if options.iter().any(|option| value == option) {
+        Ok(())
+    } else {
+        Err(format!("must be one of {:?}, got {:?}", options, value))
+    }
+}
+
+pub fn custom<E: Display>(result: std::result::Result<(), E>) -> ValidateResult {
+    result.map_err(|err| err.to_string())
+}
+
+/// Types that can be checked by the `non_empty` validator.
+pub trait IsEmpty {
+    fn is_empty(&self) -> bool;
+}
+
+impl IsEmpty for str {
+    fn is_empty(&self) -> bool {
+        str::is_empty(self)
+    }
+}
+
+impl IsEmpty for String {
+    fn is_empty(&self) -> bool {
+        String::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for Vec<T> {
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for Option<T> {
+    fn is_empty(&self) -> bool {
+        self.is_none()
+    }
+}
+
+impl<K, V, S> IsEmpty for HashMap<K, V, S> {
+    fn is_empty(&self) -> bool {
+        HashMap::is_empty(self)
+    }
+}
+
+impl<T, S> IsEmpty for HashSet<T, S> {
+    fn is_empty(&self) -> bool {
+        HashSet::is_empty(self)
+    }
+}
+
+impl<K, V> IsEmpty for BTreeMap<K, V> {
+    fn is_empty(&self) -> bool {
+        BTreeMap::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for BTreeSet<T> {
+    fn is_empty(&self) -> bool {
+        BTreeSet::is_empty(self)
+    }
+}
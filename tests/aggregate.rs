@@ -0,0 +1,59 @@
+use di_rs::*;
+
+trait Log {}
+
+#[allow(dead_code)]
+#[derive(Component)]
+struct Server {
+    #[value]
+    host: String,
+
+    #[value(range(max = 65535))]
+    port: u32,
+
+    #[value(default)]
+    workers: u32,
+
+    #[inject]
+    log: Injected<dyn Log>,
+}
+
+#[test]
+fn test_aggregate_errors() {
+    SystemBuilder::new()
+        .config_file("tests/aggregate.json")
+        .register::<Server>()
+        .run(|| {
+            let mut ctx = create_context();
+            let err = ctx.get::<Server>("server").err().unwrap();
+            let error = match &err {
+                Error::Resolve { path, error } => {
+                    assert_eq!(path, &["server"]);
+                    error
+                }
+                err => panic!("unexpected error: {}", err),
+            };
+            match error.as_ref() {
+                Error::InvalidComponent {
+                    component_name,
+                    errors,
+                } => {
+                    assert_eq!(component_name, "Server");
+                    assert_eq!(errors.len(), 4);
+                    assert!(matches!(&errors[0], Error::MissingProperty { property_name, .. } if property_name == "host"));
+                    assert!(matches!(&errors[1], Error::InvalidProperty { property_name, .. } if property_name == "port"));
+                    assert!(matches!(&errors[2], Error::InvalidProperty { property_name, .. } if property_name == "workers"));
+                    assert!(matches!(&errors[3], Error::ComponentConfigNotFound { name, .. } if name == "missing_log"));
+                }
+                err => panic!("unexpected error: {}", err),
+            }
+            assert_eq!(
+                err.to_string(),
+                "server: Component 'Server' is invalid: \
+                 Component 'Server' missing propery 'host'; \
+                 Invalid property 'port' for component 'Server': must be at most 65535, got 70000; \
+                 Invalid property 'workers' for component 'Server': invalid type: string \"four\", expected u32; \
+                 Component config 'missing_log' not found"
+            );
+        });
+}
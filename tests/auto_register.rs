@@ -0,0 +1,46 @@
+#![allow(dead_code)]
+
+use di_rs::*;
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log", auto_register)]
+struct ConsoleLog {
+    #[value]
+    prefix: String,
+}
+
+impl Log for ConsoleLog {}
+
+trait Storage {}
+
+#[derive(Component)]
+#[di(interface = "Storage", auto_register)]
+struct MemoryStorage {
+    #[inject]
+    log: Injected<dyn Log>,
+}
+
+impl Storage for MemoryStorage {}
+
+#[test]
+fn test_auto_register() {
+    let mut registry = Registry::default();
+    registry.register_all().unwrap();
+    let names = registry
+        .components()
+        .into_iter()
+        .map(|metadata| metadata.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["ConsoleLog", "MemoryStorage"]);
+
+    let system = SystemBuilder::new()
+        .config_file("tests/auto_register.json")
+        .register::<ConsoleLog>()
+        .register_all()
+        .build()
+        .unwrap();
+    let mut ctx = system.create_context();
+    assert!(ctx.get::<dyn Storage>("storage").is_ok());
+}
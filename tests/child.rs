@@ -0,0 +1,134 @@
+use di_rs::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CREATED: AtomicUsize = AtomicUsize::new(0);
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+trait Log {
+    fn prefix(&self) -> &str;
+}
+
+#[derive(Component)]
+#[di(interface = "Log", init = "init_log")]
+struct ConsoleLog {
+    #[value]
+    prefix: String,
+}
+
+fn init_log(_: &mut ConsoleLog) -> anyhow::Result<()> {
+    CREATED.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+impl Log for ConsoleLog {
+    fn prefix(&self) -> &str {
+        &self.prefix
+    }
+}
+
+impl Drop for ConsoleLog {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Component)]
+struct Session {
+    #[value]
+    user: String,
+}
+
+#[derive(Component)]
+struct Handler {
+    #[inject]
+    log: Injected<dyn Log>,
+    #[inject]
+    session: Injected<Session>,
+}
+
+#[test]
+fn test_child() {
+    SystemBuilder::new()
+        .config_file("tests/child.json")
+        .register::<ConsoleLog>()
+        .register::<Session>()
+        .register::<Handler>()
+        .run(|| {
+            let mut app = create_context();
+            app.get::<dyn Log>("log").unwrap();
+            assert_eq!(CREATED.load(Ordering::SeqCst), 1);
+
+            let session_config: Config = serde_json::from_str(
+                r#"{ "components": { "session": { "name": "Session", "props": { "user": "alice" } } } }"#,
+            )
+            .unwrap();
+            let mut session = app.child_with_config(session_config);
+            assert!(matches!(
+                app.get::<Session>("session"),
+                Err(Error::ComponentConfigNotFound { .. })
+            ));
+
+            {
+                let mut request = session.child();
+                let handler = request.get::<Handler>("handler").unwrap();
+                assert_eq!(handler.session.user, "alice");
+                assert_eq!(handler.log.prefix(), "app");
+                assert_eq!(CREATED.load(Ordering::SeqCst), 1);
+
+                let request_config: Config = serde_json::from_str(
+                    r#"{ "components": { "log": { "name": "ConsoleLog", "props": { "prefix": "request" } } } }"#,
+                )
+                .unwrap();
+                let mut scoped = request.child_with_config(request_config);
+                assert_eq!(scoped.get::<dyn Log>("log").unwrap().prefix(), "request");
+                assert_eq!(CREATED.load(Ordering::SeqCst), 2);
+            }
+            assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+
+            let mut request = session.child();
+            let handler = request.get::<Handler>("handler").unwrap();
+            assert_eq!(handler.session.user, "alice");
+            assert_eq!(session.get::<dyn Log>("log").unwrap().prefix(), "app");
+        });
+}
+
+#[test]
+fn test_child_caches_in_owner_scope() {
+    let system = SystemBuilder::new()
+        .config_file("tests/child.json")
+        .register::<ConsoleLog>()
+        .register::<Session>()
+        .register::<Handler>()
+        .build()
+        .unwrap();
+    let mut app = system.create_context();
+
+    let log = {
+        let mut request = app.child();
+        request.get::<dyn Log>("log").unwrap()
+    };
+    assert!(std::ptr::addr_eq(
+        &*log,
+        &*app.get::<dyn Log>("log").unwrap()
+    ));
+
+    let session_config = |user: &str| -> Config {
+        serde_json::from_value(serde_json::json!({
+            "components": { "session": { "name": "Session", "props": { "user": user } } }
+        }))
+        .unwrap()
+    };
+    let alice = app.child_with_config(session_config("alice"));
+    let handler = alice.child().get::<Handler>("handler").unwrap();
+    assert!(std::ptr::addr_eq(
+        &*handler,
+        &*alice.child().get::<Handler>("handler").unwrap()
+    ));
+    assert!(std::ptr::addr_eq(&*handler.log, &*log));
+
+    let bob = app.child_with_config(session_config("bob"));
+    assert_eq!(
+        bob.child().get::<Handler>("handler").unwrap().session.user,
+        "bob"
+    );
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "cli")]
+#![allow(dead_code)]
+
+use di_rs::*;
+use std::path::PathBuf;
+use std::process::Command;
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {
+    #[value]
+    prefix: String,
+}
+
+impl Log for ConsoleLog {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ChainLog {
+    #[inject]
+    next: Injected<dyn Log>,
+}
+
+impl Log for ChainLog {}
+
+fn manifest() -> PathBuf {
+    let mut registry = Registry::default();
+    registry.register::<ConsoleLog>().unwrap();
+    registry.register::<ChainLog>().unwrap();
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("cli_manifest.json");
+    std::fs::write(&path, registry.manifest().to_json()).unwrap();
+    path
+}
+
+fn di(args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_di"))
+        .args(args)
+        .arg("--manifest")
+        .arg(manifest())
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn test_cli() {
+    assert_eq!(
+        di(&[
+            "validate",
+            "--config",
+            "tests/cli.json",
+            "--feature",
+            "verbose"
+        ]),
+        (0, "ok\n".to_string())
+    );
+    assert_eq!(
+        di(&["validate", "--config", "tests/cli.json"]),
+        (
+            1,
+            "profile 'verbose': chain: property 'next': config 'console' not found\n".to_string()
+        )
+    );
+    assert_eq!(
+        di(&["validate", "--config", "tests/manifest.json"]),
+        (
+            1,
+            "console: missing property 'prefix'\n\
+             file: component 'FileLog' is not registered\n\
+             dangling: property 'next': config 'missing' not found\n\
+             circular dependency: a -> b -> a\n"
+                .to_string()
+        )
+    );
+
+    assert_eq!(
+        di(&["cycles", "--config", "tests/manifest.json"]),
+        (1, "a -> b -> a\n".to_string())
+    );
+    assert_eq!(
+        di(&["cycles", "--config", "tests/cli.json"]),
+        (0, String::new())
+    );
+
+    assert_eq!(
+        di(&[
+            "unused",
+            "--config",
+            "tests/manifest.json",
+            "--root",
+            "chain"
+        ]),
+        (
+            0,
+            "config 'a' is not used\n\
+             config 'b' is not used\n\
+             config 'dangling' is not used\n\
+             config 'file' is not used\n"
+                .to_string()
+        )
+    );
+
+    let (code, dot) = di(&["graph", "--config", "tests/cli.json"]);
+    assert_eq!(code, 0);
+    assert!(dot.starts_with("digraph"));
+    assert!(dot.contains("\"chain\" -> \"console\""));
+
+    let (code, json) = di(&["graph", "--config", "tests/cli.json", "--format", "json"]);
+    assert_eq!(code, 0);
+    let graph: DependencyGraph = serde_json::from_str(&json).unwrap();
+    assert_eq!(graph.nodes.len(), 2);
+    assert_eq!(graph.edges.len(), 1);
+
+    assert_eq!(
+        di(&["graph", "--config", "tests/cli.json", "--format", "svg"]).0,
+        2
+    );
+    assert_eq!(di(&["validate", "--config", "tests/missing.json"]).0, 2);
+    assert_eq!(di(&["validate", "--bogus"]).0, 2);
+    assert_eq!(di(&["lint", "--config", "tests/cli.json"]).0, 2);
+}
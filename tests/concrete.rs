@@ -0,0 +1,47 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Clock {
+    #[value(default)]
+    offset: i64,
+}
+
+impl Clock {
+    fn now(&self) -> i64 {
+        1000 + self.offset
+    }
+}
+
+trait Greeter {
+    fn greet(&self) -> String;
+}
+
+#[derive(Component)]
+#[di(interface = "Self", interface = "Greeter")]
+struct TimedGreeter {
+    #[inject]
+    clock: Injected<Clock>,
+}
+
+impl Greeter for TimedGreeter {
+    fn greet(&self) -> String {
+        format!("hello at {}", self.clock.now())
+    }
+}
+
+#[test]
+fn test_concrete_interface() {
+    SystemBuilder::new()
+        .config_file("tests/concrete.json")
+        .register::<Clock>()
+        .register::<TimedGreeter>()
+        .run(|| {
+            let mut ctx = create_context();
+            let clock = ctx.get::<Clock>("clock").unwrap();
+            assert_eq!(clock.now(), 1010);
+            let greeter = ctx.get::<TimedGreeter>("greeter").unwrap();
+            assert_eq!(greeter.greet(), "hello at 1010");
+            let greeter = ctx.get::<dyn Greeter>("greeter").unwrap();
+            assert_eq!(greeter.greet(), "hello at 1010");
+        });
+}
@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+
+use di_rs::*;
+
+trait Cache {
+    fn kind(&self) -> &'static str;
+}
+
+#[derive(Component)]
+#[di(interface = "Cache")]
+struct RedisCache {
+    #[value]
+    url: String,
+}
+
+impl Cache for RedisCache {
+    fn kind(&self) -> &'static str {
+        "redis"
+    }
+}
+
+fn disk_available() -> bool {
+    false
+}
+
+#[derive(Component)]
+#[di(interface = "Cache", condition = "disk_available")]
+struct DiskCache {}
+
+impl Cache for DiskCache {
+    fn kind(&self) -> &'static str {
+        "disk"
+    }
+}
+
+#[derive(Component)]
+#[di(interface = "Cache")]
+struct MemoryCache {}
+
+impl Cache for MemoryCache {
+    fn kind(&self) -> &'static str {
+        "memory"
+    }
+}
+
+fn builder() -> SystemBuilder {
+    SystemBuilder::new()
+        .config_file("tests/conditional.json")
+        .register::<RedisCache>()
+        .register::<DiskCache>()
+        .register::<MemoryCache>()
+}
+
+fn cache_kind(system: &System, key: &str) -> Option<&'static str> {
+    let mut ctx = system.create_context();
+    ctx.get::<dyn Cache>(key).ok().map(|cache| cache.kind())
+}
+
+#[test]
+fn test_conditional() {
+    let config = Config::load("tests/conditional.json").unwrap();
+    assert!(config.components.is_empty());
+    assert_eq!(config.variants["cache"].len(), 3);
+
+    let system = builder().var("env", "prod").build().unwrap();
+    assert_eq!(cache_kind(&system, "cache"), Some("redis"));
+    assert_eq!(cache_kind(&system, "fallback"), Some("memory"));
+    assert_eq!(cache_kind(&system, "metrics"), None);
+
+    let system = builder().var("env", "dev").build().unwrap();
+    assert_eq!(cache_kind(&system, "cache"), Some("memory"));
+
+    std::env::set_var("DI_CONDITIONAL_METRICS", "1");
+    let system = builder().feature("disk").build().unwrap();
+    assert_eq!(cache_kind(&system, "cache"), Some("memory"));
+    assert_eq!(cache_kind(&system, "metrics"), Some("memory"));
+}
@@ -0,0 +1,52 @@
+use di_rs::*;
+
+struct CurrentUser {
+    name: String,
+}
+
+#[derive(Component)]
+struct Handler {
+    #[value]
+    greeting: String,
+    #[inject(from_context)]
+    user: Injected<CurrentUser>,
+    #[inject(from_context, name = "request_id")]
+    id: Injected<u64>,
+}
+
+impl Handler {
+    fn message(&self) -> String {
+        format!("{} {} ({})", self.greeting, self.user.name, *self.id)
+    }
+}
+
+#[test]
+fn test_context_value() {
+    SystemBuilder::new()
+        .config_file("tests/context_value.json")
+        .register::<Handler>()
+        .run(|| {
+            let mut app = create_context();
+            app.insert(
+                "user",
+                CurrentUser {
+                    name: "alice".to_string(),
+                },
+            );
+
+            let mut request = app.child();
+            request.insert("request_id", 42u64);
+            let handler = request.get::<Handler>("handler").unwrap();
+            assert_eq!(handler.message(), "hello alice (42)");
+
+            let mut ctx = create_context();
+            ctx.insert("request_id", "not a number");
+            let err = ctx.get::<Handler>("handler").err().unwrap();
+            assert_eq!(
+                err.to_string(),
+                "handler: Component 'Handler' is invalid: \
+                 Context value 'user' of type 'context_value::CurrentUser' not found; \
+                 Context value 'request_id' is of type '&str', not 'u64'"
+            );
+        });
+}
@@ -0,0 +1,119 @@
+use di_rs::*;
+use std::sync::Arc;
+
+trait Storage: Send + Sync {
+    fn describe(&self) -> String;
+}
+
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct MemoryStorage {}
+
+impl Storage for MemoryStorage {
+    fn describe(&self) -> String {
+        "memory".to_string()
+    }
+}
+
+#[derive(Component)]
+struct Metrics {}
+
+struct Wrapped {
+    label: String,
+    inner: Arc<dyn Storage>,
+}
+
+impl Storage for Wrapped {
+    fn describe(&self) -> String {
+        format!("{}({})", self.label, self.inner.describe())
+    }
+}
+
+fn wrap(label: &str, inner: Arc<dyn Storage>) -> Arc<dyn Storage> {
+    Arc::new(Wrapped {
+        label: label.to_string(),
+        inner,
+    })
+}
+
+#[test]
+fn test_decorator() {
+    let system = SystemBuilder::new()
+        .config_file("tests/decorator.json")
+        .register::<MemoryStorage>()
+        .register::<Metrics>()
+        .add_decorator(Decorator::<dyn Storage>::new(|inner, _| Ok(wrap("retry", inner))).order(10))
+        .register_decorator::<dyn Storage, _>(|inner, ctx| {
+            ctx.get::<Metrics>("metrics")?;
+            Ok(wrap("metrics", inner))
+        })
+        .add_decorator(
+            Decorator::<dyn Storage>::new(|inner, _| Ok(wrap("log", inner)))
+                .key("db")
+                .order(-1),
+        )
+        .build()
+        .unwrap();
+
+    let mut ctx = system.create_context();
+    let db = ctx.get::<dyn Storage>("db").unwrap();
+    assert_eq!(db.describe(), "retry(metrics(log(memory)))");
+    assert_eq!(
+        ctx.get::<dyn Storage>("cache").unwrap().describe(),
+        "retry(metrics(memory))"
+    );
+    assert!(std::ptr::addr_eq(
+        &*db,
+        &*ctx.get::<dyn Storage>("db").unwrap()
+    ));
+
+    let system = SystemBuilder::new()
+        .config_file("tests/decorator.json")
+        .register::<MemoryStorage>()
+        .register_decorator::<dyn Storage, _>(|inner, ctx| {
+            ctx.get::<Metrics>("metrics")?;
+            Ok(inner)
+        })
+        .build()
+        .unwrap();
+    let err = system
+        .create_context()
+        .get::<dyn Storage>("db")
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "db: Component 'Metrics' is not registered for 'decorator::Metrics'"
+    );
+}
+
+#[test]
+fn test_decorator_scope() {
+    let system = SystemBuilder::new()
+        .config_file("tests/decorator.json")
+        .register::<MemoryStorage>()
+        .register_decorator::<dyn Storage, _>(|inner, ctx| {
+            let request_id = ctx.value::<u64>("request_id")?;
+            Ok(wrap(&format!("req{}", *request_id), inner))
+        })
+        .build()
+        .unwrap();
+
+    let app = system.create_context();
+    let mut r1 = app.child();
+    r1.insert("request_id", 1u64);
+    assert_eq!(
+        r1.get::<dyn Storage>("db").unwrap().describe(),
+        "req1(memory)"
+    );
+    let mut r2 = app.child();
+    r2.insert("request_id", 2u64);
+    assert_eq!(
+        r2.get::<dyn Storage>("db").unwrap().describe(),
+        "req2(memory)"
+    );
+    assert_eq!(
+        r1.get::<dyn Storage>("db").unwrap().describe(),
+        "req1(memory)"
+    );
+}
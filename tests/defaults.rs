@@ -0,0 +1,53 @@
+use di_rs::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Limits {
+    max_connections: u32,
+    timeout: u64,
+}
+
+#[derive(Component)]
+struct Server {
+    #[value(default = 8080)]
+    port: u16,
+
+    #[value(default = true)]
+    keep_alive: bool,
+
+    #[value(default = 0.5)]
+    ratio: f64,
+
+    #[value(default_str = "info")]
+    level: String,
+
+    #[value(default_json = r#"{ "max_connections": 100, "timeout": 30 }"#)]
+    limits: Limits,
+}
+
+#[test]
+fn test_literal_defaults() {
+    SystemBuilder::new()
+        .config_file("tests/defaults.json")
+        .register::<Server>()
+        .run(|| {
+            let mut ctx = create_context();
+
+            let server = ctx.get::<Server>("server").unwrap();
+            assert_eq!(server.port, 8080);
+            assert!(server.keep_alive);
+            assert_eq!(server.ratio, 0.5);
+            assert_eq!(server.level, "info");
+            assert_eq!(
+                server.limits,
+                Limits {
+                    max_connections: 100,
+                    timeout: 30
+                }
+            );
+
+            let server = ctx.get::<Server>("custom_server").unwrap();
+            assert_eq!(server.port, 9000);
+            assert_eq!(server.level, "debug");
+        });
+}
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+
+use di_rs::*;
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {
+    #[value]
+    prefix: String,
+}
+
+impl Log for ConsoleLog {}
+
+trait Storage {}
+
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct MemoryStorage {
+    #[inject]
+    log: Injected<dyn Log>,
+}
+
+impl Storage for MemoryStorage {}
+
+#[derive(Component)]
+struct Api {
+    #[inject]
+    storage: Injected<dyn Storage>,
+}
+
+#[derive(Component)]
+#[di(init = "init_remote")]
+struct Remote {
+    #[value]
+    url: String,
+}
+
+fn init_remote(remote: &mut Remote) -> anyhow::Result<()> {
+    anyhow::bail!("failed to connect to '{}'", remote.url)
+}
+
+#[derive(Component)]
+struct Node {
+    #[inject]
+    next: Injected<Node>,
+}
+
+#[test]
+fn test_error_context() {
+    SystemBuilder::new()
+        .config_file("tests/error_context.json")
+        .register::<ConsoleLog>()
+        .register::<MemoryStorage>()
+        .register::<Api>()
+        .register::<Remote>()
+        .register::<Node>()
+        .run(|| {
+            let mut ctx = create_context();
+
+            let err = ctx.get::<Api>("api").err().unwrap();
+            match &err {
+                Error::Resolve { path, .. } => assert_eq!(path, &["api", "storage", "log"]),
+                err => panic!("unexpected error: {}", err),
+            }
+            assert_eq!(
+                err.to_string(),
+                "api -> storage -> log: Component 'ConsoleLog' missing propery 'prefix'"
+            );
+            assert_eq!(
+                format!("{:#}", anyhow::Error::from(err)),
+                "api -> storage -> log: Component 'ConsoleLog' missing propery 'prefix'"
+            );
+
+            let err = ctx.get::<Remote>("remote").err().unwrap();
+            assert_eq!(
+                err.to_string(),
+                "remote: Other error: Failed to initialize component 'Remote': \
+                 failed to connect to 'http://localhost'"
+            );
+
+            let err = ctx.get::<Node>("a").err().unwrap();
+            assert_eq!(err.to_string(), "Circular dependency: a -> b -> a");
+        });
+}
@@ -0,0 +1,67 @@
+use di_rs::*;
+
+trait Backend {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Default)]
+struct RedisBackend;
+
+impl Backend for RedisBackend {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+}
+
+#[derive(Default)]
+struct MemoryBackend;
+
+impl Backend for MemoryBackend {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+}
+
+trait Storage {
+    fn backend(&self) -> &'static str;
+
+    fn capacity(&self) -> usize;
+}
+
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct Cache<B>
+where
+    B: Backend + Default,
+{
+    #[value(default)]
+    capacity: usize,
+
+    backend: B,
+}
+
+impl<B: Backend + Default> Storage for Cache<B> {
+    fn backend(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[test]
+fn test_generic_component() {
+    SystemBuilder::new()
+        .config_file("tests/generic.json")
+        .register_as::<Cache<RedisBackend>>("RedisCache")
+        .register_as::<Cache<MemoryBackend>>("MemoryCache")
+        .run(|| {
+            let mut ctx = create_context();
+            let redis = ctx.get::<dyn Storage>("redis").unwrap();
+            assert_eq!(redis.backend(), "redis");
+            let memory = ctx.get::<dyn Storage>("memory").unwrap();
+            assert_eq!(memory.backend(), "memory");
+            assert_eq!(memory.capacity(), 100);
+        });
+}
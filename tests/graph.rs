@@ -0,0 +1,87 @@
+use di_rs::*;
+
+trait Log {}
+
+trait Storage {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {}
+
+impl Log for ConsoleLog {}
+
+#[allow(dead_code)]
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct MemoryStorage {
+    #[inject]
+    log: Injected<dyn Log>,
+
+    #[inject(name = "backup.log")]
+    backup_log: Injected<dyn Log>,
+}
+
+impl Storage for MemoryStorage {}
+
+#[test]
+fn test_dependency_graph() {
+    SystemBuilder::new()
+        .config_file("tests/graph.json")
+        .register::<ConsoleLog>()
+        .register::<MemoryStorage>()
+        .run(|| {
+            let graph = system().dependency_graph();
+
+            let keys = graph
+                .nodes
+                .iter()
+                .map(|node| node.key.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(keys, vec!["broken", "log", "storage", "unknown"]);
+            assert_eq!(graph.nodes[1].interfaces, vec!["dyn graph::Log"]);
+            assert!(graph.nodes[3].type_name.is_none());
+
+            let edges = graph
+                .edges
+                .iter()
+                .map(|edge| {
+                    (
+                        edge.from.as_str(),
+                        edge.to.as_str(),
+                        edge.prop.as_str(),
+                        edge.unresolved.as_deref(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                edges,
+                vec![
+                    (
+                        "broken",
+                        "storage",
+                        "log",
+                        Some("component 'MemoryStorage' does not implement 'dyn graph::Log'")
+                    ),
+                    (
+                        "broken",
+                        "missing",
+                        "backup.log",
+                        Some("config 'missing' not found")
+                    ),
+                    ("storage", "log", "log", None),
+                    ("storage", "log", "backup.log", None),
+                ]
+            );
+            assert_eq!(graph.unresolved_edges().count(), 2);
+
+            let dot = graph.to_dot();
+            assert!(dot.starts_with("digraph components {"));
+            assert!(dot.contains("\"storage\" -> \"log\" [label=\"log\"];"));
+            assert!(dot.contains(
+                "\"broken\" -> \"missing\" [label=\"backup.log\", color=red, style=dashed];"
+            ));
+
+            let json: serde_json::Value = serde_json::from_str(&graph.to_json()).unwrap();
+            assert_eq!(json["edges"][2]["interface"], "dyn graph::Log");
+        });
+}
@@ -0,0 +1,94 @@
+#![allow(dead_code)]
+
+use di_rs::*;
+use std::sync::{Arc, Mutex};
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {
+    #[value]
+    prefix: String,
+}
+
+impl Log for ConsoleLog {}
+
+trait Storage {}
+
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct MemoryStorage {
+    #[inject]
+    log: Injected<dyn Log>,
+}
+
+impl Storage for MemoryStorage {}
+
+#[test]
+fn test_hooks() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let record = |kind: &'static str| {
+        let events = events.clone();
+        move |event: &ResolutionEvent| {
+            events.lock().unwrap().push(format!(
+                "{} {} {} {}{}",
+                kind,
+                event.key,
+                event.type_name,
+                event.interface,
+                event
+                    .error
+                    .map(|err| format!(": {}", err))
+                    .unwrap_or_default()
+            ))
+        }
+    };
+
+    let system = SystemBuilder::new()
+        .config_file("tests/hooks.json")
+        .register::<ConsoleLog>()
+        .register::<MemoryStorage>()
+        .on_create(record("create"))
+        .on_error(record("error"))
+        .on_dispose(record("dispose"))
+        .add_decorator(
+            Decorator::<dyn Storage>::new(|inner, ctx| {
+                ctx.get::<dyn Log>("missing")?;
+                Ok(inner)
+            })
+            .key("decorated"),
+        )
+        .build()
+        .unwrap();
+
+    {
+        let mut ctx = system.create_context();
+        ctx.get::<dyn Storage>("storage").unwrap();
+        ctx.get::<dyn Log>("log").unwrap();
+        assert!(ctx.get::<dyn Log>("broken").is_err());
+        assert!(ctx.get::<dyn Log>("storage").is_err());
+        assert!(ctx.get::<dyn Storage>("decorated").is_err());
+    }
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            "create log hooks::ConsoleLog dyn hooks::Log",
+            "create storage hooks::MemoryStorage dyn hooks::Storage",
+            "error broken hooks::ConsoleLog dyn hooks::Log: \
+             broken: Component 'ConsoleLog' missing propery 'prefix'",
+            "error storage hooks::MemoryStorage dyn hooks::Log: \
+             Component 'MemoryStorage' not implemented for 'dyn hooks::Log', \
+             it is registered for 'dyn hooks::Storage'",
+            "create decorated hooks::MemoryStorage dyn hooks::Storage",
+            "error missing FileLog dyn hooks::Log: \
+             Component 'FileLog' is not registered for 'dyn hooks::Log'",
+            "error decorated hooks::MemoryStorage dyn hooks::Storage: \
+             decorated: Component 'FileLog' is not registered for 'dyn hooks::Log'",
+            "dispose decorated hooks::MemoryStorage dyn hooks::Storage",
+            "dispose storage hooks::MemoryStorage dyn hooks::Storage",
+            "dispose log hooks::ConsoleLog dyn hooks::Log",
+        ]
+    );
+}
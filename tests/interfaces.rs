@@ -0,0 +1,43 @@
+use di_rs::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+trait Storage {
+    fn set(&self, value: usize);
+}
+
+trait HealthCheck {
+    fn writes(&self) -> usize;
+}
+
+#[derive(Component)]
+#[di(interface = "Storage", interface = "HealthCheck")]
+struct Database {
+    writes: AtomicUsize,
+}
+
+impl Storage for Database {
+    fn set(&self, _value: usize) {
+        self.writes.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl HealthCheck for Database {
+    fn writes(&self) -> usize {
+        self.writes.load(Ordering::SeqCst)
+    }
+}
+
+#[test]
+fn test_multiple_interfaces() {
+    SystemBuilder::new()
+        .config_file("tests/interfaces.json")
+        .register::<Database>()
+        .run(|| {
+            let mut ctx = create_context();
+            let storage = ctx.get::<dyn Storage>("db").unwrap();
+            storage.set(1);
+            storage.set(2);
+            let health = ctx.get::<dyn HealthCheck>("db").unwrap();
+            assert_eq!(health.writes(), 2);
+        });
+}
@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+
+use di_rs::*;
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {
+    #[value]
+    prefix: String,
+}
+
+impl Log for ConsoleLog {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ChainLog {
+    #[inject]
+    next: Injected<dyn Log>,
+}
+
+impl Log for ChainLog {}
+
+#[test]
+fn test_manifest() {
+    let mut registry = Registry::default();
+    registry.register::<ConsoleLog>().unwrap();
+    registry.register::<ChainLog>().unwrap();
+
+    let manifest = registry.manifest();
+    let manifest: Manifest = serde_json::from_str(&manifest.to_json()).unwrap();
+    let names = manifest
+        .components
+        .iter()
+        .map(|metadata| metadata.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["ChainLog", "ConsoleLog"]);
+
+    let config = Config::load("tests/manifest.json").unwrap();
+    assert_eq!(
+        config.validate(&manifest.components),
+        vec![
+            "console: missing property 'prefix'",
+            "file: component 'FileLog' is not registered",
+            "dangling: property 'next': config 'missing' not found",
+            "circular dependency: a -> b -> a",
+        ]
+    );
+
+    let graph = DependencyGraph::build(&config, &manifest.components);
+    assert_eq!(graph.cycles(), vec![vec!["a", "b", "a"]]);
+    assert_eq!(graph.unreachable(&[]), vec!["chain", "dangling", "file"]);
+    assert_eq!(
+        graph.unreachable(&["chain"]),
+        vec!["a", "b", "dangling", "file"]
+    );
+}
@@ -0,0 +1,40 @@
+use di_rs::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Limits {
+    max_connections: u32,
+    timeout: u64,
+}
+
+#[derive(Component)]
+struct Server {
+    #[value(name = "tls.cert_path")]
+    cert_path: String,
+
+    #[value(name = "tls.key.path", default_str = "key.pem")]
+    key_path: String,
+
+    #[value(flatten)]
+    limits: Limits,
+}
+
+#[test]
+fn test_nested_props() {
+    SystemBuilder::new()
+        .config_file("tests/nested.json")
+        .register::<Server>()
+        .run(|| {
+            let mut ctx = create_context();
+            let server = ctx.get::<Server>("server").unwrap();
+            assert_eq!(server.cert_path, "cert.pem");
+            assert_eq!(server.key_path, "key.pem");
+            assert_eq!(
+                server.limits,
+                Limits {
+                    max_connections: 100,
+                    timeout: 30
+                }
+            );
+        });
+}
@@ -0,0 +1,51 @@
+use di_rs::*;
+
+trait Log {}
+
+trait Storage {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {}
+
+impl Log for ConsoleLog {}
+
+#[test]
+fn test_not_found() {
+    SystemBuilder::new()
+        .config_file("tests/not_found.json")
+        .register::<ConsoleLog>()
+        .run(|| {
+            let mut ctx = create_context();
+
+            let err = ctx.get::<dyn Log>("consol_log").err().unwrap();
+            assert!(matches!(
+                &err,
+                Error::ComponentConfigNotFound { name, suggestions }
+                    if name == "consol_log" && suggestions == &["console_log"]
+            ));
+            assert_eq!(
+                err.to_string(),
+                "Component config 'consol_log' not found, did you mean 'console_log'?"
+            );
+
+            let err = ctx.get::<dyn Log>("file_log").err().unwrap();
+            assert!(matches!(
+                &err,
+                Error::ComponentNotFound { component_name, suggestions, .. }
+                    if component_name == "ConsoleLg" && suggestions == &["ConsoleLog"]
+            ));
+            assert_eq!(
+                err.to_string(),
+                "Component 'ConsoleLg' is not registered for 'dyn not_found::Log', \
+                 did you mean 'ConsoleLog'?"
+            );
+
+            let err = ctx.get::<dyn Storage>("console_log").err().unwrap();
+            assert_eq!(
+                err.to_string(),
+                "Component 'ConsoleLog' not implemented for 'dyn not_found::Storage', \
+                 it is registered for 'dyn not_found::Log'"
+            );
+        });
+}
@@ -0,0 +1,84 @@
+use di_rs::*;
+
+trait Storage {
+    fn kind(&self) -> &'static str;
+}
+
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct MemoryStorage {}
+
+impl Storage for MemoryStorage {
+    fn kind(&self) -> &'static str {
+        "memory"
+    }
+}
+
+#[derive(Component)]
+#[di(name = "MemoryStorage", interface = "Storage")]
+struct FakeStorage {}
+
+impl Storage for FakeStorage {
+    fn kind(&self) -> &'static str {
+        "fake"
+    }
+}
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {}
+
+impl Log for ConsoleLog {}
+
+#[test]
+fn test_duplicate() {
+    let mut registry = Registry::default();
+    registry.register::<MemoryStorage>().unwrap();
+    assert!(matches!(
+        registry.register::<MemoryStorage>(),
+        Err(Error::DuplicateComponent { .. })
+    ));
+    let err = registry.register::<FakeStorage>().err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Component 'MemoryStorage' is already registered by 'override::MemoryStorage', \
+         use `register_override` to replace it with 'override::FakeStorage'"
+    );
+
+    let mut other = Registry::default();
+    other.register::<ConsoleLog>().unwrap();
+    other.register::<FakeStorage>().unwrap();
+    assert!(matches!(
+        registry.merge(other),
+        Err(Error::DuplicateComponent { .. })
+    ));
+    assert!(registry.find("ConsoleLog").is_none());
+
+    let err = SystemBuilder::new()
+        .register::<MemoryStorage>()
+        .register::<FakeStorage>()
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::DuplicateComponent { .. }));
+}
+
+#[test]
+fn test_override() {
+    let mut other = Registry::default();
+    other.register::<ConsoleLog>().unwrap();
+
+    let system = SystemBuilder::new()
+        .config_file("tests/override.json")
+        .register::<MemoryStorage>()
+        .register_override::<FakeStorage>()
+        .registry(other)
+        .build()
+        .unwrap();
+
+    let mut ctx = system.create_context();
+    assert_eq!(ctx.get::<dyn Storage>("storage").unwrap().kind(), "fake");
+    assert!(ctx.get::<dyn Log>("log").is_ok());
+}
@@ -0,0 +1,128 @@
+#![allow(dead_code)]
+
+use di_rs::*;
+use std::collections::{HashMap, HashSet};
+
+trait Log {
+    fn level(&self) -> &str;
+}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {
+    #[value]
+    prefix: String,
+    #[value]
+    level: String,
+}
+
+impl Log for ConsoleLog {
+    fn level(&self) -> &str {
+        &self.level
+    }
+}
+
+trait Storage {
+    fn kind(&self) -> &'static str;
+}
+
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct MemoryStorage {
+    #[inject]
+    log: Injected<dyn Log>,
+}
+
+impl Storage for MemoryStorage {
+    fn kind(&self) -> &'static str {
+        "memory"
+    }
+}
+
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct FileStorage {
+    #[inject]
+    log: Injected<dyn Log>,
+    #[value]
+    path: String,
+}
+
+impl Storage for FileStorage {
+    fn kind(&self) -> &'static str {
+        "file"
+    }
+}
+
+fn builder() -> SystemBuilder {
+    SystemBuilder::new()
+        .config_file("tests/profiles.json")
+        .register::<ConsoleLog>()
+        .register::<MemoryStorage>()
+        .register::<FileStorage>()
+}
+
+fn storage(system: &System) -> (&'static str, String) {
+    let mut ctx = system.create_context();
+    let storage = ctx.get::<dyn Storage>("storage").unwrap();
+    let log = ctx.get::<dyn Log>("log").unwrap();
+    (storage.kind(), log.level().to_string())
+}
+
+#[test]
+fn test_profiles() {
+    let system = builder().build().unwrap();
+    assert_eq!(storage(&system), ("memory", "debug".to_string()));
+
+    let system = builder().profile("prod").build().unwrap();
+    assert_eq!(storage(&system), ("file", "warn".to_string()));
+
+    std::env::set_var(PROFILE_ENV, "prod");
+    let system = builder().build().unwrap();
+    assert_eq!(storage(&system), ("file", "warn".to_string()));
+    std::env::remove_var(PROFILE_ENV);
+
+    let err = builder().profile("prd").build().err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Profile 'prd' not found, did you mean 'prod'?"
+    );
+
+    let system = builder()
+        .profile("verbose")
+        .feature("trace")
+        .build()
+        .unwrap();
+    assert_eq!(storage(&system), ("memory", "trace".to_string()));
+
+    let system = builder().build().unwrap();
+    let components = system.registry().manifest().components;
+    for features in [HashSet::new(), HashSet::from(["trace".to_string()])] {
+        assert_eq!(
+            system
+                .config()
+                .validate_profiles(&components, &HashMap::new(), &features),
+            vec!["profile 'broken': storage: property 'log': config 'log' not found"]
+        );
+    }
+
+    let system = builder().profile("prod").build().unwrap();
+    assert_eq!(
+        system
+            .config()
+            .validate_profiles(&components, &HashMap::new(), &HashSet::new()),
+        vec!["profile 'broken': storage: property 'log': config 'log' not found"]
+    );
+
+    let mut config = Config::load("tests/profiles.json").unwrap();
+    config
+        .components
+        .get_mut("log")
+        .unwrap()
+        .props
+        .insert("prefix".to_string(), "patched".into());
+    let prod = config.profile("prod").unwrap();
+    assert_eq!(prod.components["log"].props["prefix"], "patched");
+    assert_eq!(prod.components["log"].props["level"], "warn");
+    assert_eq!(prod.profiles.len(), config.profiles.len());
+}
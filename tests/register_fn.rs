@@ -0,0 +1,83 @@
+use di_rs::*;
+use std::sync::Arc;
+
+trait Runtime: Send + Sync {
+    fn id(&self) -> u32;
+}
+
+struct TokioRuntime(u32);
+
+impl Runtime for TokioRuntime {
+    fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+trait Storage {
+    fn capacity(&self) -> usize;
+    fn runtime(&self) -> u32;
+}
+
+struct MemoryStorage {
+    capacity: usize,
+    runtime: Injected<dyn Runtime>,
+}
+
+impl Storage for MemoryStorage {
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn runtime(&self) -> u32 {
+        self.runtime.id()
+    }
+}
+
+#[derive(Component)]
+struct Api {
+    #[inject]
+    storage: Injected<dyn Storage>,
+}
+
+#[test]
+fn test_register_fn() {
+    let runtime: Arc<dyn Runtime> = Arc::new(TokioRuntime(7));
+
+    let system = SystemBuilder::new()
+        .config_file("tests/register_fn.json")
+        .register_instance("Runtime", runtime.clone())
+        .register_fn::<dyn Storage, _>("MemoryStorage", |ctx, props| {
+            let capacity = props["capacity"].as_u64().unwrap() as usize;
+            let runtime = ctx.get::<dyn Runtime>(props["runtime"].as_str().unwrap())?;
+            Ok(Arc::new(MemoryStorage { capacity, runtime }))
+        })
+        .register::<Api>()
+        .strict(true)
+        .build()
+        .unwrap();
+
+    let mut ctx = system.create_context();
+    let api = ctx.get::<Api>("api").unwrap();
+    assert_eq!(api.storage.capacity(), 16);
+    assert_eq!(api.storage.runtime(), 7);
+    assert!(std::ptr::addr_eq(
+        &*ctx.get::<dyn Runtime>("runtime").unwrap(),
+        Arc::as_ptr(&runtime)
+    ));
+
+    let mut registry = Registry::default();
+    registry
+        .register_instance("Runtime", runtime.clone())
+        .unwrap();
+    let manifest = registry.manifest();
+    assert!(manifest.components[0].props.is_empty());
+    assert!(manifest.components[0].props_unknown);
+
+    let err = SystemBuilder::new()
+        .register_instance("Runtime", runtime.clone())
+        .register_instance("Runtime", runtime)
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::DuplicateComponent { .. }));
+}
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+
+use di_rs::*;
+use std::time::Duration;
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log", init = "init_log")]
+struct SlowLog {}
+
+fn init_log(_: &mut SlowLog) -> anyhow::Result<()> {
+    std::thread::sleep(Duration::from_millis(50));
+    Ok(())
+}
+
+impl Log for SlowLog {}
+
+trait Storage {}
+
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct MemoryStorage {
+    #[inject]
+    log: Injected<dyn Log>,
+}
+
+impl Storage for MemoryStorage {}
+
+#[derive(Component)]
+struct Api {
+    #[inject]
+    storage: Injected<dyn Storage>,
+}
+
+#[test]
+fn test_startup_report() {
+    SystemBuilder::new()
+        .config_file("tests/report.json")
+        .register::<SlowLog>()
+        .register::<MemoryStorage>()
+        .register::<Api>()
+        .run(|| {
+            let mut ctx = create_context();
+            ctx.get::<Api>("api").unwrap();
+            ctx.get::<dyn Storage>("storage").unwrap();
+
+            let report = system().startup_report();
+            let keys = report
+                .components
+                .iter()
+                .map(|record| record.key.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(keys[0], "log");
+            assert_eq!(keys.len(), 3);
+            assert_eq!(report.depth(), 3);
+
+            let api = &report.components[keys.iter().position(|key| *key == "api").unwrap()];
+            assert_eq!(api.component, "Api");
+            assert_eq!(api.interface, "report::Api");
+            assert_eq!(api.depth, 3);
+            assert!(api.elapsed >= Duration::from_millis(50));
+            assert!(api.self_elapsed < Duration::from_millis(50));
+            assert!(report.total() >= Duration::from_millis(50));
+            assert!(report.to_string().starts_with("3 components created in "));
+
+            let request_config: Config = serde_json::from_str(
+                r#"{ "components": { "request_storage": { "name": "MemoryStorage", "props": { "log": "log" } } } }"#,
+            )
+            .unwrap();
+            let mut request = ctx.child_with_config(request_config);
+            request.get::<dyn Storage>("request_storage").unwrap();
+            create_context().get::<Api>("api").unwrap();
+            assert_eq!(system().startup_report().components.len(), 3);
+        });
+}
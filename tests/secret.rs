@@ -0,0 +1,55 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Database {
+    #[value]
+    inline_password: Secret<String>,
+
+    #[value]
+    env_password: Secret<String>,
+
+    #[value]
+    file_password: Secret<String>,
+
+    #[value(default)]
+    replica_password: Option<Secret<String>>,
+}
+
+#[test]
+fn test_secret() {
+    let secret = Secret::new("hunter2".to_string());
+    assert_eq!(format!("{:?}", secret), "Secret(***)");
+    assert_eq!(secret.expose(), "hunter2");
+
+    let path = std::env::temp_dir().join("di_rs_secret_test");
+    std::fs::write(&path, "from-file\n").unwrap();
+    std::env::set_var("DI_RS_SECRET_TEST", "from-env");
+
+    let mut config: serde_json::Value = serde_json::from_str(include_str!("secret.json")).unwrap();
+    config["components"]["db"]["props"]["file_password"]["file"] = path.to_str().unwrap().into();
+    let config_path = std::env::temp_dir().join("di_rs_secret_test.json");
+    std::fs::write(&config_path, config.to_string()).unwrap();
+
+    SystemBuilder::new()
+        .config_file(&config_path)
+        .register::<Database>()
+        .run(|| {
+            let mut ctx = create_context();
+            let db = ctx.get::<Database>("db").unwrap();
+            assert_eq!(db.inline_password.expose(), "inline");
+            assert_eq!(db.env_password.expose(), "from-env");
+            assert_eq!(db.file_password.expose(), "from-file");
+            assert!(db.replica_password.is_none());
+
+            let err = ctx.get::<Database>("invalid").err().unwrap().to_string();
+            assert!(err.contains("invalid secret value"));
+            assert!(!err.contains("12345"));
+
+            let err = ctx
+                .get::<Database>("missing_env")
+                .err()
+                .unwrap()
+                .to_string();
+            assert!(err.contains("environment variable 'DI_RS_MISSING_SECRET' is not set"));
+        });
+}
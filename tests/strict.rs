@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+
+use di_rs::*;
+use serde::Deserialize;
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {
+    #[value]
+    prefix: String,
+}
+
+impl Log for ConsoleLog {}
+
+#[derive(Deserialize)]
+struct FlatOptions {
+    prefix: String,
+}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct FlatLog {
+    #[value(flatten)]
+    options: FlatOptions,
+}
+
+impl Log for FlatLog {}
+
+#[derive(Component)]
+struct MemoryStorage {
+    #[inject]
+    log: Injected<dyn Log>,
+    #[value(name = "tls.cert_path")]
+    cert_path: String,
+}
+
+#[derive(Component)]
+struct Server {
+    #[value(name = "tls.cert_path", default)]
+    cert_path: String,
+}
+
+#[test]
+fn test_strict() {
+    SystemBuilder::new()
+        .config_file("tests/strict.json")
+        .register::<ConsoleLog>()
+        .register::<FlatLog>()
+        .register::<MemoryStorage>()
+        .register::<Server>()
+        .strict(true)
+        .run(|| {
+            let mut ctx = create_context();
+
+            let err = ctx.get::<dyn Log>("log").err().unwrap();
+            assert_eq!(
+                err.to_string(),
+                "log: Component 'ConsoleLog' does not have property 'prefx', did you mean 'prefix'?"
+            );
+
+            assert!(ctx.get::<MemoryStorage>("storage").is_ok());
+            assert!(ctx.get::<dyn Log>("flat").is_ok());
+
+            let err = ctx.get::<Server>("server").err().unwrap();
+            assert_eq!(
+                err.to_string(),
+                "server: Component 'Server' does not have property 'tls.cert_pth', did you mean 'tls.cert_path'?"
+            );
+
+            assert_eq!(system().unreferenced_components(), vec!["log", "server", "unused"]);
+        });
+}
@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+
+use di_rs::*;
+use std::sync::Arc;
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {
+    #[value]
+    prefix: String,
+}
+
+impl Log for ConsoleLog {}
+
+trait Storage {
+    fn get(&self) -> &'static str;
+}
+
+#[derive(Component)]
+#[di(interface = "Storage")]
+struct MemoryStorage {
+    #[inject]
+    log: Injected<dyn Log>,
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self) -> &'static str {
+        "memory"
+    }
+}
+
+#[derive(Component)]
+struct Api {
+    #[inject]
+    storage: Injected<dyn Storage>,
+}
+
+struct MockStorage;
+
+impl Storage for MockStorage {
+    fn get(&self) -> &'static str {
+        "mock"
+    }
+}
+
+fn builder() -> SystemBuilder {
+    SystemBuilder::new()
+        .config_file("tests/test_container.json")
+        .register::<ConsoleLog>()
+        .register::<MemoryStorage>()
+        .register::<Api>()
+}
+
+#[test]
+fn test_container() {
+    let mock: Arc<dyn Storage> = Arc::new(MockStorage);
+    let mut container = TestContainer::new(builder()).override_instance("storage", mock);
+    let api = container.get::<Api>("api").unwrap();
+    assert_eq!(api.storage.get(), "mock");
+    assert_eq!(container.resolved(), vec!["api", "storage"]);
+
+    let err = container.get::<dyn Log>("storage").err().unwrap();
+    assert!(matches!(err, Error::NotImplemented { .. }));
+
+    let mut container = TestContainer::new(builder());
+    let api = container.get::<Api>("api").unwrap();
+    assert_eq!(api.storage.get(), "memory");
+    assert_eq!(container.resolved(), vec!["api", "log", "storage"]);
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "tracing")]
+#![allow(dead_code)]
+
+use di_rs::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+trait Log {}
+
+#[derive(Component)]
+#[di(interface = "Log")]
+struct ConsoleLog {}
+
+impl Log for ConsoleLog {}
+
+#[derive(Component)]
+struct Api {
+    #[inject]
+    log: Injected<dyn Log>,
+}
+
+type Fields = HashMap<String, String>;
+
+/// Collects the fields of every span, in creation order.
+#[derive(Clone, Default)]
+struct Spans(Arc<Mutex<Vec<Fields>>>);
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl Subscriber for Spans {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut spans = self.0.lock().unwrap();
+        let mut fields = Fields::new();
+        fields.insert("name".to_string(), span.metadata().name().to_string());
+        span.record(&mut FieldVisitor(&mut fields));
+        spans.push(fields);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.0.lock().unwrap();
+        values.record(&mut FieldVisitor(&mut spans[span.into_u64() as usize - 1]));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn test_tracing() {
+    let system = SystemBuilder::new()
+        .config_file("tests/tracing.json")
+        .register::<ConsoleLog>()
+        .register::<Api>()
+        .build()
+        .unwrap();
+    let spans = Spans::default();
+
+    tracing::subscriber::with_default(spans.clone(), || {
+        let mut ctx = system.create_context();
+        ctx.get::<Api>("api").unwrap();
+        ctx.get::<Api>("api").unwrap();
+    });
+
+    let spans = spans.0.lock().unwrap();
+    let summary = spans
+        .iter()
+        .map(|fields| {
+            (
+                fields["name"].as_str(),
+                fields["key"].as_str(),
+                fields["cache"].as_str(),
+                fields.contains_key("elapsed_us"),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("di.get", "api", "miss", true),
+            ("di.get", "log", "miss", true),
+            ("di.get", "api", "hit", false),
+        ]
+    );
+    assert_eq!(spans[0]["component"], "Api");
+    assert_eq!(spans[1]["interface"], "dyn tracing::Log");
+}
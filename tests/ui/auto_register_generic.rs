@@ -0,0 +1,11 @@
+use di_rs::*;
+
+trait Backend {}
+
+#[derive(Component)]
+#[di(auto_register)]
+struct Cache<B: Backend> {
+    backend: Option<B>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use di_rs::*;
+
+#[derive(Component)]
+#[di(auto_register = true)]
+struct ConsoleLog {}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use di_rs::*;
+
+fn redis_enabled() -> Option<bool> {
+    None
+}
+
+#[derive(Component)]
+#[di(condition = "redis_enabled")]
+struct RedisCache {}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(default = "default_port")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(default = "default_port")]
+    port: u16,
+}
+
+fn default_port() -> String {
+    "8080".to_string()
+}
+
+fn main() {}
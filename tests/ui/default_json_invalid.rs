@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(default_json = "{ port: 8080 }")]
+    options: std::collections::HashMap<String, u16>,
+}
+
+fn main() {}
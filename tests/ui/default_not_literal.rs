@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(default = b"8080")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use di_rs::*;
+
+#[derive(Component)]
+#[di]
+struct ConsoleLog {}
+
+fn main() {}
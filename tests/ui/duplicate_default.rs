@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(default = 8080, default_json = "8080")]
+    port: u16,
+}
+
+fn main() {}
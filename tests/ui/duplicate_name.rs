@@ -0,0 +1,7 @@
+use di_rs::*;
+
+#[derive(Component)]
+#[di(name = "A", name = "B")]
+struct ConsoleLog {}
+
+fn main() {}
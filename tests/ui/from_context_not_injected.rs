@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Handler {
+    #[inject(from_context)]
+    request_id: u64,
+}
+
+fn main() {}
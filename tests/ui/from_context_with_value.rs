@@ -0,0 +1,11 @@
+use di_rs::*;
+
+trait Log {}
+
+#[derive(Component)]
+struct Server {
+    #[inject(from_context = true)]
+    log: Injected<dyn Log>,
+}
+
+fn main() {}
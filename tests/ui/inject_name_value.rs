@@ -0,0 +1,11 @@
+use di_rs::*;
+
+trait Log {}
+
+#[derive(Component)]
+struct Server {
+    #[inject = "log"]
+    log: Injected<dyn Log>,
+}
+
+fn main() {}
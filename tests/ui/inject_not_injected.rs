@@ -0,0 +1,13 @@
+use di_rs::*;
+use std::sync::Arc;
+
+#[derive(Component)]
+struct Clock {}
+
+#[derive(Component)]
+struct Timer {
+    #[inject]
+    clock: Arc<Clock>,
+}
+
+fn main() {}
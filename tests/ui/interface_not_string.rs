@@ -0,0 +1,7 @@
+use di_rs::*;
+
+#[derive(Component)]
+#[di(interface = 1)]
+struct ConsoleLog {}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use di_rs::*;
+
+#[derive(Component)]
+#[di(init = "1init")]
+struct ConsoleLog {}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use di_rs::*;
+
+#[derive(Component)]
+#[di(interface = "dyn Log")]
+struct ConsoleLog {}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use di_rs::*;
+
+#[derive(Component)]
+#[di(name = 1)]
+struct ConsoleLog {}
+
+fn main() {}
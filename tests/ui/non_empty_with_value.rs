@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(non_empty = true)]
+    host: String,
+}
+
+fn main() {}
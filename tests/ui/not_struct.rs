@@ -0,0 +1,8 @@
+use di_rs::*;
+
+#[derive(Component)]
+enum Log {
+    Console,
+}
+
+fn main() {}
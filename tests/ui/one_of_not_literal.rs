@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(one_of(localhost))]
+    host: String,
+}
+
+fn main() {}
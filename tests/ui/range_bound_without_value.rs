@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(range(min))]
+    port: u16,
+}
+
+fn main() {}
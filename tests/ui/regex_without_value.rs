@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(regex)]
+    host: String,
+}
+
+fn main() {}
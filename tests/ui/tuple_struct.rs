@@ -0,0 +1,6 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Port(u16);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use di_rs::*;
+
+#[derive(Component)]
+#[di("ConsoleLog")]
+struct ConsoleLog {}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use di_rs::*;
+
+trait Log {}
+
+#[derive(Component)]
+#[di(nmae = "ConsoleLog", interface = "Log")]
+struct ConsoleLog {}
+
+impl Log for ConsoleLog {}
+
+fn main() {}
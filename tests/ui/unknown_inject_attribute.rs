@@ -0,0 +1,12 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Clock {}
+
+#[derive(Component)]
+struct Timer {
+    #[inject(config = "clock")]
+    clock: Injected<Clock>,
+}
+
+fn main() {}
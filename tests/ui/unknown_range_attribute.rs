@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(range(min = 1, mx = 10))]
+    port: u16,
+}
+
+fn main() {}
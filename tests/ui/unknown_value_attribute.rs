@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct ConsoleLog {
+    #[value(defualt)]
+    prefix: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(validate = "1check")]
+    host: String,
+}
+
+fn main() {}
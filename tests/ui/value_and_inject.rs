@@ -0,0 +1,13 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Clock {}
+
+#[derive(Component)]
+struct Timer {
+    #[value]
+    #[inject]
+    clock: Injected<Clock>,
+}
+
+fn main() {}
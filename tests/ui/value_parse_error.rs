@@ -0,0 +1,9 @@
+use di_rs::*;
+
+#[derive(Component)]
+struct Server {
+    #[value(name "port")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,111 @@
+use di_rs::*;
+
+fn power_of_two(value: &u32) -> std::result::Result<(), String> {
+    if value.is_power_of_two() {
+        Ok(())
+    } else {
+        Err(format!("{} is not a power of two", value))
+    }
+}
+
+#[derive(Component)]
+struct Server {
+    #[value(range(min = 1, max = 65535))]
+    port: u32,
+
+    #[value(regex = "^[a-z]+$")]
+    host: String,
+
+    #[value(non_empty)]
+    users: Vec<String>,
+
+    #[value(one_of = ["debug", "info"], default_str = "info")]
+    level: String,
+
+    #[value(validate = "power_of_two", default = 2)]
+    workers: u32,
+}
+
+fn create_server(ctx: &mut Context, name: &str) -> Result<Injected<Server>> {
+    ctx.get::<Server>(name)
+}
+
+fn assert_invalid(err: Error, property: &str, expected: &str) {
+    let err = match err {
+        Error::Resolve { error, .. } => *error,
+        err => panic!("unexpected error: {}", err),
+    };
+    match err {
+        Error::InvalidProperty {
+            property_name,
+            message,
+            ..
+        } => {
+            assert_eq!(property_name, property);
+            assert_eq!(message, expected);
+        }
+        err => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn test_validate() {
+    let mut registry = Registry::default();
+    registry.register::<Server>().unwrap();
+    let metadata = registry.components();
+    assert_eq!(metadata.len(), 1);
+    assert_eq!(
+        metadata[0].props[0].validators,
+        vec![Validator::Range {
+            min: Some(1.into()),
+            max: Some(65535.into())
+        }]
+    );
+    assert_eq!(
+        metadata[0].props[3].validators,
+        vec![Validator::OneOf(vec!["debug".into(), "info".into()])]
+    );
+    assert_eq!(
+        metadata[0].props[4].validators,
+        vec![Validator::Custom("power_of_two".to_string())]
+    );
+
+    SystemBuilder::new()
+        .config_file("tests/validate.json")
+        .register::<Server>()
+        .run(|| {
+            let mut ctx = create_context();
+            let server = create_server(&mut ctx, "valid").unwrap();
+            assert_eq!(server.port, 8080);
+            assert_eq!(server.host, "localhost");
+            assert_eq!(server.users, vec!["admin".to_string()]);
+            assert_eq!(server.level, "info");
+            assert_eq!(server.workers, 2);
+
+            assert_invalid(
+                create_server(&mut ctx, "bad_port").err().unwrap(),
+                "port",
+                "must be at most 65535, got 70000",
+            );
+            assert_invalid(
+                create_server(&mut ctx, "bad_host").err().unwrap(),
+                "host",
+                "'Local' does not match the pattern '^[a-z]+$'",
+            );
+            assert_invalid(
+                create_server(&mut ctx, "bad_users").err().unwrap(),
+                "users",
+                "must not be empty",
+            );
+            assert_invalid(
+                create_server(&mut ctx, "bad_level").err().unwrap(),
+                "level",
+                "must be one of [\"debug\", \"info\"], got \"trace\"",
+            );
+            assert_invalid(
+                create_server(&mut ctx, "bad_workers").err().unwrap(),
+                "workers",
+                "3 is not a power of two",
+            );
+        });
+}